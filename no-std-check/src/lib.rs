@@ -0,0 +1,44 @@
+//! Compiles the copy-paste bigint module without the standard library,
+//! the same way it would be used on an embedded target.
+#![no_std]
+
+#[path = "../../src/bigint.rs"]
+pub mod bigint;
+
+use core::fmt::Write;
+
+type I40 = bigint::BigInt<40>;
+
+pub const FACTORIAL_30: I40 = {
+    let mut result = I40::from_i128(1);
+    let mut i = 0;
+    while i < 30 {
+        result = result.mul(I40::from_i128(i + 1));
+        i += 1;
+    }
+    result
+};
+
+const _: () = assert!(FACTORIAL_30.equal(I40::from_str("265252859812191058636308480000000")));
+const _: () = assert!(FACTORIAL_30.div(I40::from_i128(1_000_000_007)).1.equal(I40::from_i128(109361473)));
+
+/// Formats a number into a fixed buffer, returns the number of bytes written
+pub fn format_into(value: &I40, buffer: &mut [u8]) -> Option<usize> {
+    struct Buffer<'a> {
+        bytes: &'a mut [u8],
+        len: usize,
+    }
+
+    impl Write for Buffer<'_> {
+        fn write_str(&mut self, s: &str) -> core::fmt::Result {
+            let end = self.len + s.len();
+            self.bytes.get_mut(self.len..end).ok_or(core::fmt::Error)?.copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    let mut buffer = Buffer { bytes: buffer, len: 0 };
+    write!(buffer, "{}", value).ok()?;
+    Some(buffer.len)
+}
@@ -8,15 +8,77 @@
 /// +------+---------------------+--------+--------+-----+-------+--------+
 /// | bool |  u8   |  u8   | ... |  u8    |  u8    | ... |  u8   |   u64  |
 /// ```
-#[derive(Debug, Clone, Copy)]
+///
+/// Digits outside of `[MAX_SIZE - current_size, MAX_SIZE)` are always zero,
+/// so every loop only visits the significant digits of its operands and
+/// a small value costs the same in any `BigInt<MAX_SIZE>`.
+#[derive(Clone, Copy)]
 pub struct BigInt<const MAX_SIZE: usize> {
     is_negative: bool,
     digits: [u8; MAX_SIZE],
     current_size: usize,
 }
 
+/// An error returned when parsing a BigInt from a string fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseBigIntError {
+    /// the string contains no digits
+    Empty,
+    /// the string contains a character that is not a digit
+    InvalidDigit(char),
+    /// the number has more than MAX_SIZE digits
+    Overflow,
+}
+
+impl core::fmt::Display for ParseBigIntError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseBigIntError::Empty => write!(f, "cannot parse integer from empty string"),
+            ParseBigIntError::InvalidDigit(c) => write!(f, "invalid digit found in string: {:?}", c),
+            ParseBigIntError::Overflow => write!(f, "number too large to fit in target type"),
+        }
+    }
+}
+
+impl core::error::Error for ParseBigIntError {}
+
+/// An error returned when a BigInt does not fit into a primitive integer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryFromBigIntError;
+
+impl core::fmt::Display for TryFromBigIntError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "out of range integral type conversion attempted")
+    }
+}
+
+impl core::error::Error for TryFromBigIntError {}
+
+/// An error returned when an f64 can't be converted to a BigInt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FromF64Error {
+    /// the value is NaN
+    NaN,
+    /// the value is positive or negative infinity
+    Infinite,
+    /// the integer part has more than MAX_SIZE digits
+    OutOfRange,
+}
+
+impl core::fmt::Display for FromF64Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FromF64Error::NaN => write!(f, "cannot convert NaN to BigInt"),
+            FromF64Error::Infinite => write!(f, "cannot convert an infinite value to BigInt"),
+            FromF64Error::OutOfRange => write!(f, "number too large to fit in target type"),
+        }
+    }
+}
+
+impl core::error::Error for FromF64Error {}
 
-impl<const MAX_SIZE: usize> std::ops::Add for BigInt<MAX_SIZE> {
+
+impl<const MAX_SIZE: usize> core::ops::Add for BigInt<MAX_SIZE> {
     type Output = Self;
 
     fn add(self, other: Self) -> Self {
@@ -24,7 +86,7 @@ impl<const MAX_SIZE: usize> std::ops::Add for BigInt<MAX_SIZE> {
     }
 }
 
-impl<const MAX_SIZE: usize> std::ops::Sub for BigInt<MAX_SIZE> {
+impl<const MAX_SIZE: usize> core::ops::Sub for BigInt<MAX_SIZE> {
     type Output = Self;
 
     fn sub(self, other: Self) -> Self {
@@ -32,7 +94,7 @@ impl<const MAX_SIZE: usize> std::ops::Sub for BigInt<MAX_SIZE> {
     }
 }
 
-impl<const MAX_SIZE: usize> std::ops::Mul for BigInt<MAX_SIZE> {
+impl<const MAX_SIZE: usize> core::ops::Mul for BigInt<MAX_SIZE> {
     type Output = Self;
 
     fn mul(self, other: Self) -> Self {
@@ -40,7 +102,7 @@ impl<const MAX_SIZE: usize> std::ops::Mul for BigInt<MAX_SIZE> {
     }
 }
 
-impl<const MAX_SIZE: usize> std::ops::Div for BigInt<MAX_SIZE> {
+impl<const MAX_SIZE: usize> core::ops::Div for BigInt<MAX_SIZE> {
     type Output = Self;
 
     fn div(self, other: Self) -> Self {
@@ -48,7 +110,7 @@ impl<const MAX_SIZE: usize> std::ops::Div for BigInt<MAX_SIZE> {
     }
 }
 
-impl<const MAX_SIZE: usize> std::ops::Rem for BigInt<MAX_SIZE> {
+impl<const MAX_SIZE: usize> core::ops::Rem for BigInt<MAX_SIZE> {
     type Output = Self;
 
     fn rem(self, other: Self) -> Self {
@@ -56,85 +118,588 @@ impl<const MAX_SIZE: usize> std::ops::Rem for BigInt<MAX_SIZE> {
     }
 }
 
-impl<const MAX_SIZE: usize> std::cmp::PartialEq for BigInt<MAX_SIZE> {
+impl<const MAX_SIZE: usize> core::ops::Neg for BigInt<MAX_SIZE> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.neg()
+    }
+}
+
+// shifts move decimal places, a << n is a * 10^n and a >> n is a / 10^n,
+// they are not binary shifts
+impl<const MAX_SIZE: usize> core::ops::Shl<usize> for BigInt<MAX_SIZE> {
+    type Output = Self;
+
+    fn shl(self, n: usize) -> Self {
+        self.shl_pow10(n)
+    }
+}
+
+impl<const MAX_SIZE: usize> core::ops::Shr<usize> for BigInt<MAX_SIZE> {
+    type Output = Self;
+
+    fn shr(self, n: usize) -> Self {
+        self.shr_pow10(n)
+    }
+}
+
+impl<const MAX_SIZE: usize> core::ops::AddAssign for BigInt<MAX_SIZE> {
+    fn add_assign(&mut self, other: Self) {
+        *self = self.add(other);
+    }
+}
+
+impl<const MAX_SIZE: usize> core::ops::SubAssign for BigInt<MAX_SIZE> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = self.sub(other);
+    }
+}
+
+impl<const MAX_SIZE: usize> core::ops::MulAssign for BigInt<MAX_SIZE> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = self.mul(other);
+    }
+}
+
+impl<const MAX_SIZE: usize> core::ops::DivAssign for BigInt<MAX_SIZE> {
+    fn div_assign(&mut self, other: Self) {
+        *self = self.div(other).0;
+    }
+}
+
+impl<const MAX_SIZE: usize> core::ops::RemAssign for BigInt<MAX_SIZE> {
+    fn rem_assign(&mut self, other: Self) {
+        *self = self.div(other).1;
+    }
+}
+
+impl<const MAX_SIZE: usize> core::ops::ShlAssign<usize> for BigInt<MAX_SIZE> {
+    fn shl_assign(&mut self, n: usize) {
+        *self = self.shl_pow10(n);
+    }
+}
+
+impl<const MAX_SIZE: usize> core::ops::ShrAssign<usize> for BigInt<MAX_SIZE> {
+    fn shr_assign(&mut self, n: usize) {
+        *self = self.shr_pow10(n);
+    }
+}
+
+impl<const MAX_SIZE: usize> core::iter::Sum for BigInt<MAX_SIZE> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::from_i128(0), |acc, x| acc.add(x))
+    }
+}
+
+impl<'a, const MAX_SIZE: usize> core::iter::Sum<&'a Self> for BigInt<MAX_SIZE> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::from_i128(0), |acc, x| acc.add(*x))
+    }
+}
+
+impl<const MAX_SIZE: usize> core::iter::Product for BigInt<MAX_SIZE> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::from_i128(1), |acc, x| acc.mul(x))
+    }
+}
+
+impl<'a, const MAX_SIZE: usize> core::iter::Product<&'a Self> for BigInt<MAX_SIZE> {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::from_i128(1), |acc, x| acc.mul(*x))
+    }
+}
+
+impl<const MAX_SIZE: usize> core::cmp::PartialEq for BigInt<MAX_SIZE> {
     fn eq(&self, other: &Self) -> bool {
         self.equal(*other)
     }
 }
 
-impl<const MAX_SIZE: usize> std::cmp::PartialOrd for BigInt<MAX_SIZE> {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+impl<const MAX_SIZE: usize> core::cmp::Eq for BigInt<MAX_SIZE> {}
+
+impl<const MAX_SIZE: usize> core::cmp::PartialOrd for BigInt<MAX_SIZE> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const MAX_SIZE: usize> core::cmp::Ord for BigInt<MAX_SIZE> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         if self.less(*other) {
-            Some(std::cmp::Ordering::Less)
+            core::cmp::Ordering::Less
         } else if self.equal(*other) {
-            Some(std::cmp::Ordering::Equal)
+            core::cmp::Ordering::Equal
         } else {
-            Some(std::cmp::Ordering::Greater)
+            core::cmp::Ordering::Greater
         }
     }
 }
 
+// compare through to_i128, so that values outside of the i128 range
+// and i128 values that don't fit into MAX_SIZE digits are both handled
+impl<const MAX_SIZE: usize> core::cmp::PartialEq<i128> for BigInt<MAX_SIZE> {
+    fn eq(&self, other: &i128) -> bool {
+        self.to_i128() == Some(*other)
+    }
+}
 
+impl<const MAX_SIZE: usize> core::cmp::PartialEq<BigInt<MAX_SIZE>> for i128 {
+    fn eq(&self, other: &BigInt<MAX_SIZE>) -> bool {
+        other == self
+    }
+}
 
-impl<const MAX_SIZE: usize> std::fmt::Display for BigInt<MAX_SIZE> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut result = String::with_capacity(self.current_size + 1);
-        if self.is_negative {
-            result.push('-');
-        }
-        let mut i = MAX_SIZE - self.current_size;
-        while i < MAX_SIZE {
-            result.push((self.digits[i] + '0' as u8) as char);
-            i += 1;
+impl<const MAX_SIZE: usize> core::cmp::PartialOrd<i128> for BigInt<MAX_SIZE> {
+    fn partial_cmp(&self, other: &i128) -> Option<core::cmp::Ordering> {
+        match self.to_i128() {
+            Some(value) => value.partial_cmp(other),
+            None if self.is_negative => Some(core::cmp::Ordering::Less),
+            None => Some(core::cmp::Ordering::Greater),
         }
-        write!(f, "{}", result)
+    }
+}
+
+impl<const MAX_SIZE: usize> core::cmp::PartialOrd<BigInt<MAX_SIZE>> for i128 {
+    fn partial_cmp(&self, other: &BigInt<MAX_SIZE>) -> Option<core::cmp::Ordering> {
+        other.partial_cmp(self).map(core::cmp::Ordering::reverse)
+    }
+}
+
+impl<const MAX_SIZE: usize> core::hash::Hash for BigInt<MAX_SIZE> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        // only the significant digits matter, zero is never negative
+        (self.is_negative && !self.is_zero()).hash(state);
+        self.current_size.hash(state);
+        self.digits[MAX_SIZE - self.current_size..].hash(state);
+    }
+}
+
+
+
+impl<const MAX_SIZE: usize> core::fmt::Display for BigInt<MAX_SIZE> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.write_padded(f, self.digits_len(f.alternate()), |f| self.write_digits(f))
+    }
+}
+
+impl<const MAX_SIZE: usize> core::fmt::LowerExp for BigInt<MAX_SIZE> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.write_exp(f, 'e')
+    }
+}
+
+impl<const MAX_SIZE: usize> core::fmt::UpperExp for BigInt<MAX_SIZE> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.write_exp(f, 'E')
+    }
+}
+
+macro_rules! impl_from_primitive {
+    ($constructor:ident, $wide:ty, $($t:ty),*) => {
+        $(
+            impl<const MAX_SIZE: usize> From<$t> for BigInt<MAX_SIZE> {
+                fn from(num: $t) -> Self {
+                    Self::$constructor(num as $wide)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_primitive!(from_u128, u128, u8, u16, u32, u64, u128);
+impl_from_primitive!(from_i128, i128, i8, i16, i32, i64, i128);
+
+impl<const MAX_SIZE: usize> From<bool> for BigInt<MAX_SIZE> {
+    fn from(b: bool) -> Self {
+        Self::from_bool(b)
+    }
+}
+
+impl<const MAX_SIZE: usize> TryFrom<BigInt<MAX_SIZE>> for i128 {
+    type Error = TryFromBigIntError;
+
+    fn try_from(value: BigInt<MAX_SIZE>) -> Result<Self, Self::Error> {
+        value.to_i128().ok_or(TryFromBigIntError)
+    }
+}
+
+// serialized as a decimal string, which keeps the full precision
+#[cfg(feature = "serde")]
+impl<const MAX_SIZE: usize> serde::Serialize for BigInt<MAX_SIZE> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const MAX_SIZE: usize> serde::Deserialize<'de> for BigInt<MAX_SIZE> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Self::try_from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+impl<const MAX_SIZE: usize> Default for BigInt<MAX_SIZE> {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl<const MAX_SIZE: usize> core::fmt::Debug for BigInt<MAX_SIZE> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "BigInt<{}>({})", MAX_SIZE, self)
+    }
+}
+
+impl<const MAX_SIZE: usize> core::str::FromStr for BigInt<MAX_SIZE> {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from_str(s)
+    }
+}
+
+// there is no panicking From<&str>, since it would come with a blanket
+// TryFrom<&str> that can't fail, use the const from_str for that instead
+impl<const MAX_SIZE: usize> TryFrom<&str> for BigInt<MAX_SIZE> {
+    type Error = ParseBigIntError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::try_from_str(s)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const MAX_SIZE: usize> core::fmt::LowerHex for BigInt<MAX_SIZE> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.pad_integral(!self.is_negative, "0x", &self.abs().to_str_radix(16))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const MAX_SIZE: usize> core::fmt::UpperHex for BigInt<MAX_SIZE> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.pad_integral(!self.is_negative, "0X", &self.abs().to_str_radix(16).to_uppercase())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const MAX_SIZE: usize> core::fmt::Binary for BigInt<MAX_SIZE> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.pad_integral(!self.is_negative, "0b", &self.abs().to_str_radix(2))
     }
 }
 
 impl<const MAX_SIZE: usize> BigInt<MAX_SIZE> {
     pub const DEFAULT: Self = Self { is_negative: false, digits: [0; MAX_SIZE], current_size: 1};
 
+    /// The largest representable value, MAX_SIZE nines
+    pub const MAX: Self = Self { is_negative: false, digits: [9; MAX_SIZE], current_size: MAX_SIZE };
+
+    /// The smallest representable value, MAX_SIZE nines with a minus sign
+    pub const MIN: Self = Self { is_negative: true, digits: [9; MAX_SIZE], current_size: MAX_SIZE };
+
+    /// Operands with more digits than this are multiplied with Karatsuba
+    const KARATSUBA_THRESHOLD: usize = 64;
+
+    /// Parses a decimal string, panicking on anything that isn't a digit or on a sign
+    /// without digits so that typos in constants fail to compile, for example (illustrative only, doctests
+    /// don't run for this binary crate)
+    ///
+    /// ```compile_fail
+    /// use bigint::BigInt;
+    /// const X: BigInt<10> = BigInt::from_str("12x45");
+    /// ```
     pub const fn from_str(s: &str) -> Self {
         let mut result = Self::DEFAULT;
 
         let chars = s.as_bytes();
-        result.is_negative = chars[0] == '-' as u8;
+        let sign_len = (!chars.is_empty() && (chars[0] == b'-' || chars[0] == b'+')) as usize;
+        assert!(chars.len() > sign_len, "no digits in from_str");
+        result.is_negative = chars[0] == b'-';
+
+        let mut j = sign_len;
+        while j < chars.len() {
+            assert!(chars[j].is_ascii_digit(), "invalid digit in from_str");
+            j += 1;
+        }
+
+        // leading zeros don't count towards MAX_SIZE
+        let mut start = sign_len;
+        while start + 1 < chars.len() && chars[start] == b'0' {
+            start += 1;
+        }
+        assert!(chars.len() - start <= MAX_SIZE, "number too large for BigInt<MAX_SIZE>");
 
-        let mut i = MAX_SIZE + result.is_negative as usize - chars.len();
+        let mut i = MAX_SIZE + start - chars.len();
         result.current_size = MAX_SIZE - i;
 
         while i < MAX_SIZE {
-            result.digits[i] = chars[i + chars.len() - MAX_SIZE] - '0' as u8;
+            result.digits[i] = chars[i + chars.len() - MAX_SIZE] - b'0';
             i += 1;
         }  
 
+        result.trimmed().normalize()
+    }
+
+    /// Like from_str, but returns default instead of panicking
+    /// if s has no digits, an invalid character or too many digits
+    pub const fn from_str_or(s: &str, default: Self) -> Self {
+        let chars = s.as_bytes();
+        if chars.is_empty() {
+            return default;
+        }
+        let is_negative = chars[0] == b'-';
+        let sign_len = (chars[0] == b'-' || chars[0] == b'+') as usize;
+        if chars.len() == sign_len {
+            return default;
+        }
+
+        let mut start = chars.len();
+        let mut j = sign_len;
+        while j < chars.len() {
+            if !chars[j].is_ascii_digit() {
+                return default;
+            }
+            if chars[j] != b'0' && start == chars.len() {
+                start = j;
+            }
+            j += 1;
+        }
+        if chars.len() - start > MAX_SIZE {
+            return default;
+        }
+
+        let mut result = Self::DEFAULT;
+        let mut i = start;
+        while i < chars.len() {
+            result.digits[MAX_SIZE - (chars.len() - i)] = chars[i] - b'0';
+            i += 1;
+        }
+        result.current_size = const_helpers::max!(chars.len() - start, 1);
+        result.is_negative = is_negative;
+        result.normalize()
+    }
+
+    /// Checked version of from_str for runtime input
+    pub fn try_from_str(s: &str) -> Result<Self, ParseBigIntError> {
+        let (is_negative, digits) = Self::split_sign(s);
+
+        if digits.is_empty() {
+            return Err(ParseBigIntError::Empty);
+        }
+        if let Some(c) = digits.chars().find(|c| !c.is_ascii_digit()) {
+            return Err(ParseBigIntError::InvalidDigit(c));
+        }
+
+        let digits = digits.trim_start_matches('0');
+        if digits.len() > MAX_SIZE {
+            return Err(ParseBigIntError::Overflow);
+        }
+
+        let mut result = Self::DEFAULT;
+        for (i, c) in digits.bytes().rev().enumerate() {
+            result.digits[MAX_SIZE - 1 - i] = c - b'0';
+        }
+        result.current_size = const_helpers::max!(digits.len(), 1);
+        result.is_negative = is_negative && !result.is_zero();
+        Ok(result)
+    }
+
+    /// Parses a number written in the given radix, digits above 9 may be upper or lower case
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseBigIntError> {
+        assert!((2..=36).contains(&radix), "radix must be in the range 2..=36");
+        let (is_negative, digits) = Self::split_sign(s);
+
+        if digits.is_empty() {
+            return Err(ParseBigIntError::Empty);
+        }
+
+        // radix itself may not fit into MAX_SIZE, so it is never made a BigInt
+        let mut result = Self::DEFAULT;
+        for c in digits.chars() {
+            let digit = match c.to_digit(radix) {
+                Some(digit) => digit,
+                None => return Err(ParseBigIntError::InvalidDigit(c)),
+            };
+            let (next, overflow) = result.mul_add_small(radix, digit);
+            if overflow {
+                return Err(ParseBigIntError::Overflow);
+            }
+            result = next;
+        }
+        result.is_negative = is_negative && !result.is_zero();
+        Ok(result)
+    }
+
+    /// Formats the number in the given radix, digits above 9 are lower case
+    // BigInt is Copy but can be large, so conversions borrow it
+    #[allow(clippy::wrong_self_convention)]
+    #[cfg(feature = "std")]
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        assert!((2..=36).contains(&radix), "radix must be in the range 2..=36");
+        let mut digits = Vec::new();
+        let mut value = self.abs();
+        loop {
+            let (quotient, digit) = value.div_rem_small(radix);
+            digits.push(char::from_digit(digit, radix).unwrap());
+            value = quotient;
+            if value.is_zero() {
+                break;
+            }
+        }
+        if self.is_negative {
+            digits.push('-');
+        }
+        digits.iter().rev().collect()
+    }
+
+    /// Returns the sign (true if negative) and the big-endian base-256 magnitude,
+    /// the magnitude of zero is empty
+    #[allow(clippy::wrong_self_convention)]
+    #[cfg(feature = "std")]
+    pub fn to_bytes_be(&self) -> (bool, Vec<u8>) {
+        let mut bytes = Vec::new();
+        let mut value = self.abs();
+        while !value.is_zero() {
+            let (quotient, remainder) = value.div_rem_small(256);
+            bytes.push(remainder as u8);
+            value = quotient;
+        }
+        bytes.reverse();
+        (self.is_negative, bytes)
+    }
+
+    /// Builds a number from a sign (true if negative) and a big-endian base-256 magnitude
+    pub fn from_bytes_be(is_negative: bool, bytes: &[u8]) -> Self {
+        // 256 itself may not fit into MAX_SIZE, so it is never made a BigInt
+        let mut result = Self::DEFAULT;
+        for &byte in bytes {
+            let (next, overflow) = result.mul_add_small(256, byte as u32);
+            assert!(!overflow, "number too large for BigInt<MAX_SIZE>");
+            result = next;
+        }
+        result.is_negative = is_negative && !result.is_zero();
         result
     }
 
-    pub const fn from_i128(num: i128) -> Self {
+    /// Builds a number from a sign (true if negative) and big-endian decimal digits,
+    /// panics if a digit is above 9 or the digits don't fit into MAX_SIZE
+    pub const fn from_digits_be(is_negative: bool, digits: &[u8]) -> Self {
+        let mut start = 0;
+        while start + 1 < digits.len() && digits[start] == 0 {
+            start += 1;
+        }
+        assert!(digits.len() - start <= MAX_SIZE, "number too large for BigInt<MAX_SIZE>");
+
         let mut result = Self::DEFAULT;
+        let mut i = start;
+        while i < digits.len() {
+            assert!(digits[i] <= 9, "invalid digit in from_digits_be");
+            result.digits[MAX_SIZE - (digits.len() - i)] = digits[i];
+            i += 1;
+        }
+        result.current_size = const_helpers::max!(digits.len() - start, 1);
+        result.is_negative = is_negative;
+        result.normalize()
+    }
+
+    /// Builds a number from a sign and a full buffer of decimal digits, most significant first,
+    /// in the same right-aligned layout BigInt uses internally
+    pub const fn from_raw_digits(is_negative: bool, digits: [u8; MAX_SIZE]) -> Self {
+        let mut i = 0;
+        while i < MAX_SIZE {
+            debug_assert!(digits[i] <= 9, "invalid digit in from_raw_digits");
+            i += 1;
+        }
+        Self { is_negative, digits, current_size: MAX_SIZE }.trimmed().normalize()
+    }
+
+    /// Splits an optional leading sign from the digits
+    fn split_sign(s: &str) -> (bool, &str) {
+        match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        }
+    }
+
+    pub const fn from_i128(num: i128) -> Self {
+        // unsigned_abs doesn't overflow on i128::MIN
+        let mut result = Self::from_u128(num.unsigned_abs());
         result.is_negative = num < 0;
-        let mut num = num.abs();
-        let mut i = MAX_SIZE - 1;
+        result
+    }
+
+    pub const fn from_u128(num: u128) -> Self {
+        let mut result = Self::DEFAULT;
+        let mut num = num;
+        let mut size = 0;
         while num > 0 {
-            result.digits[i] = (num % 10) as u8;
+            assert!(size < MAX_SIZE, "number too large for BigInt<MAX_SIZE>");
+            result.digits[MAX_SIZE - 1 - size] = (num % 10) as u8;
             num /= 10;
-            i -= 1;
+            size += 1;
         }
-        result.current_size = MAX_SIZE - i - 1;
+        result.current_size = const_helpers::max!(size, 1);
         result
     }
 
-    pub const fn const_clone(&self) -> Self {
+    /// BigInt::from_bool(b) returns 1 if b is true and 0 otherwise
+    pub const fn from_bool(b: bool) -> Self {
         let mut result = Self::DEFAULT;
-        result.is_negative = self.is_negative;
-        result.current_size = self.current_size;
+        result.digits[MAX_SIZE - 1] = b as u8;
+        result
+    }
+
+    /// BigInt::pow10(n) returns 10^n, a one followed by n zeros
+    pub const fn pow10(exp: usize) -> Self {
+        assert!(exp < MAX_SIZE, "number too large for BigInt<MAX_SIZE>");
+        let mut result = Self::DEFAULT;
+        result.digits[MAX_SIZE - 1 - exp] = 1;
+        result.current_size = exp + 1;
+        result
+    }
+
+    /// Returns the value as i128, or None if it does not fit
+    #[allow(clippy::wrong_self_convention)]
+    pub const fn to_i128(&self) -> Option<i128> {
+        // accumulate the negative value since it has the larger range
+        let mut result: i128 = 0;
         let mut i = MAX_SIZE - self.current_size;
         while i < MAX_SIZE {
-            result.digits[i] = self.digits[i];
+            result = match result.checked_mul(10) {
+                Some(result) => result,
+                None => return None,
+            };
+            result = match result.checked_sub(self.digits[i] as i128) {
+                Some(result) => result,
+                None => return None,
+            };
             i += 1;
         }
-        result
+
+        if self.is_negative {
+            Some(result)
+        } else {
+            result.checked_neg()
+        }
+    }
+
+    /// Kept for compatibility, BigInt is Copy so this is just *self
+    pub const fn const_clone(&self) -> Self {
+        *self
+    }
+
+    /// Same as Self::MAX
+    pub const fn max_value() -> Self {
+        Self::MAX
+    }
+
+    /// Same as Self::MIN
+    pub const fn min_value() -> Self {
+        Self::MIN
     }
 
     pub const fn max_size(&self) -> usize {
@@ -145,6 +710,22 @@ impl<const MAX_SIZE: usize> BigInt<MAX_SIZE> {
         self.current_size
     }
 
+    /// Number of decimal digits, zero has one digit
+    pub const fn num_digits(&self) -> usize {
+        self.current_size
+    }
+
+    /// Sum of the decimal digits, the sign is ignored
+    pub const fn sum_of_digits(&self) -> u32 {
+        let mut sum = 0;
+        let mut i = MAX_SIZE - self.current_size;
+        while i < MAX_SIZE {
+            sum += self.digits[i] as u32;
+            i += 1;
+        }
+        sum
+    }
+
     pub const fn is_zero(&self) -> bool {
         self.current_size == 1 && self.digits[MAX_SIZE - 1] == 0
     }
@@ -152,54 +733,148 @@ impl<const MAX_SIZE: usize> BigInt<MAX_SIZE> {
     pub const fn is_even(&self) -> bool {
         self.digits[MAX_SIZE - 1] % 2 == 0
     }
+
+    pub const fn is_odd(&self) -> bool {
+        self.digits[MAX_SIZE - 1] % 2 == 1
+    }
+
+    pub const fn is_one(&self) -> bool {
+        !self.is_negative && self.current_size == 1 && self.digits[MAX_SIZE - 1] == 1
+    }
+
+    pub const fn is_negative_one(&self) -> bool {
+        self.is_negative && self.current_size == 1 && self.digits[MAX_SIZE - 1] == 1
+    }
     
     pub const fn add(self, other: Self) -> Self {
+        let (result, overflow) = self.add_overflowing(other);
+        debug_assert!(!overflow, "addition overflow");
+        result
+    }
+
+    /// a.checked_add(b) returns None if a + b doesn't fit into MAX_SIZE digits
+    pub const fn checked_add(self, other: Self) -> Option<Self> {
+        match self.add_overflowing(other) {
+            (result, false) => Some(result),
+            (_, true) => None,
+        }
+    }
+
+    /// Adds keeping only the last MAX_SIZE digits,
+    /// the flag is set if the carry out of the buffer was dropped
+    const fn add_overflowing(self, other: Self) -> (Self, bool) {
         let mut result: Self;
+        let mut overflow = false;
 
         // if signs are the same
         // we can simply add the numbers
         if !(self.is_negative ^ other.is_negative) { 
-            result = Self::add_abs(self, other);
+            (result, overflow) = Self::add_abs(self, other);
             result.is_negative = self.is_negative;
         } else {
             result = Self::sub_abs(self, other);
             result.is_negative = if self.abs_less(other) { other.is_negative } else { self.is_negative };
-            if result.is_zero() {result.is_negative = false} 
         }
-        result
+        (result.normalize(), overflow)
     }
 
     #[inline(always)]
-    const fn add_abs(self, other: Self) -> Self {
+    const fn add_abs(self, other: Self) -> (Self, bool) {
         let mut result: BigInt<MAX_SIZE> = Self {current_size: 0, ..Self::DEFAULT};
+        let size = const_helpers::max!(self.current_size, other.current_size);
         let mut carry = 0;
-        let mut i = MAX_SIZE - 1;
-        while i > MAX_SIZE - const_helpers::max!(self.current_size, other.current_size) - 1 {
+        let mut i = MAX_SIZE;
+        while i > MAX_SIZE - size {
+            i -= 1;
             let sum = self.digits[i] as i16 + other.digits[i] as i16 + carry;
             result.digits[i] = (sum % 10) as u8;
             carry = sum / 10;
-            i -= 1;
         }
+
+        let mut overflow = false;
         if carry > 0 {
-            result.digits[i] = carry as u8;
-            result.current_size = 1;
+            if i == 0 {
+                overflow = true;
+            } else {
+                result.digits[i - 1] = carry as u8;
+                result.current_size = 1;
+            }
         }
-        result.current_size += const_helpers::max!(self.current_size, other.current_size);
-        result
+        result.current_size += size;
+
+        // the dropped carry may leave leading zeros
+        if overflow {
+            result = result.trimmed();
+        }
+        (result, overflow)
     }
 
     pub const fn sub(self, other: Self) -> Self {
+        let (result, overflow) = self.sub_overflowing(other);
+        debug_assert!(!overflow, "subtraction overflow");
+        result
+    }
+
+    /// a.checked_sub(b) returns None if a - b doesn't fit into MAX_SIZE digits
+    pub const fn checked_sub(self, other: Self) -> Option<Self> {
+        match self.sub_overflowing(other) {
+            (result, false) => Some(result),
+            (_, true) => None,
+        }
+    }
+
+    /// a.saturating_add(b) clamps the result to [min_value(), max_value()]
+    pub const fn saturating_add(self, other: Self) -> Self {
+        match self.add_overflowing(other) {
+            (result, false) => result,
+            (_, true) => if self.is_negative { Self::min_value() } else { Self::max_value() },
+        }
+    }
+
+    /// a.wrapping_add(b) keeps only the last MAX_SIZE digits of a + b,
+    /// so the magnitude wraps modulo 10^MAX_SIZE and the sign is kept
+    pub const fn wrapping_add(self, other: Self) -> Self {
+        self.add_overflowing(other).0
+    }
+
+    /// a.overflowing_add(b) returns a.wrapping_add(b) and whether a + b didn't fit
+    pub const fn overflowing_add(self, other: Self) -> (Self, bool) {
+        self.add_overflowing(other)
+    }
+
+    /// a.saturating_sub(b) clamps the result to [min_value(), max_value()]
+    pub const fn saturating_sub(self, other: Self) -> Self {
+        match self.sub_overflowing(other) {
+            (result, false) => result,
+            (_, true) => if self.is_negative { Self::min_value() } else { Self::max_value() },
+        }
+    }
+
+    /// a.saturating_sub_to_zero(b) returns max(a - b, 0)
+    pub const fn saturating_sub_to_zero(self, other: Self) -> Self {
+        if self.greater(other) {
+            self.sub(other)
+        } else {
+            Self::DEFAULT
+        }
+    }
+
+    /// Subtracts keeping only the last MAX_SIZE digits,
+    /// the flag is set if the carry out of the buffer was dropped
+    const fn sub_overflowing(self, other: Self) -> (Self, bool) {
         let mut result: Self;
-  
+        let mut overflow = false;
+
         if !(self.is_negative ^ other.is_negative) {
             result = Self::sub_abs(self, other);
             result.is_negative = self.less(other);
         } else {
-            result = Self::add_abs(self, other);
+            // the magnitude can only grow if the signs differ
+            (result, overflow) = Self::add_abs(self, other);
             result.is_negative = self.is_negative;
         }
 
-        result
+        (result.normalize(), overflow)
     }
 
     #[inline(always)]
@@ -215,10 +890,11 @@ impl<const MAX_SIZE: usize> BigInt<MAX_SIZE> {
         }
 
         let mut borrow = 0;
-        let mut i = MAX_SIZE - 1;
+        let mut i = MAX_SIZE;
         let max_size = const_helpers::max!(self.current_size, other.current_size);
         let mut final_size = max_size;
-        while i > MAX_SIZE - max_size - 1 {
+        while i > MAX_SIZE - max_size {
+            i -= 1;
             let diff = 10 + greater.digits[i] as i16 - smaller.digits[i] as i16 - borrow;
             (result.digits[i], borrow) = if diff >= 10 { ((diff - 10) as u8, 0i16) } else { (diff as u8, 1i16) };
 
@@ -227,7 +903,6 @@ impl<const MAX_SIZE: usize> BigInt<MAX_SIZE> {
             } else {
                 final_size = max_size;
             }
-            i -= 1;
         }
         result.current_size = const_helpers::max!(final_size, 1);
         result
@@ -248,7 +923,7 @@ impl<const MAX_SIZE: usize> BigInt<MAX_SIZE> {
 
         let size = self.current_size;
 
-        let mut i = MAX_SIZE - size - 1;
+        let mut i = MAX_SIZE - size;
         while i < MAX_SIZE {
             if self.digits[i] < other.digits[i] {
                 return true;
@@ -289,12 +964,12 @@ impl<const MAX_SIZE: usize> BigInt<MAX_SIZE> {
 
         let size = self.current_size;
 
-        let mut i = MAX_SIZE - size - 1;
+        let mut i = MAX_SIZE - size;
         while i < MAX_SIZE {
             if self.digits[i] < other.digits[i] {
-                return if self.is_negative { false } else { true };
+                return !self.is_negative;
             } else if self.digits[i] > other.digits[i] {
-                return if self.is_negative { true } else { false };
+                return self.is_negative;
             }
             i += 1;
         }
@@ -323,29 +998,350 @@ impl<const MAX_SIZE: usize> BigInt<MAX_SIZE> {
 
 
     pub const fn mul(self, other: Self) -> Self {
+        let (result, overflow) = self.mul_overflowing(other);
+        debug_assert!(!overflow, "multiplication overflow");
+        result
+    }
+
+    /// a.checked_mul(b) returns None if a * b doesn't fit into MAX_SIZE digits
+    pub const fn checked_mul(self, other: Self) -> Option<Self> {
+        match self.mul_overflowing(other) {
+            (result, false) => Some(result),
+            (_, true) => None,
+        }
+    }
+
+    /// a.saturating_mul(b) clamps the result to [min_value(), max_value()]
+    pub const fn saturating_mul(self, other: Self) -> Self {
+        match self.mul_overflowing(other) {
+            (result, false) => result,
+            (_, true) => if self.is_negative ^ other.is_negative { Self::min_value() } else { Self::max_value() },
+        }
+    }
+
+    /// a.wrapping_mul(b) keeps only the last MAX_SIZE digits of a * b,
+    /// so the magnitude wraps modulo 10^MAX_SIZE and the sign is kept
+    pub const fn wrapping_mul(self, other: Self) -> Self {
+        self.mul_overflowing(other).0
+    }
+
+    /// a.overflowing_mul(b) returns a.wrapping_mul(b) and whether a * b didn't fit
+    pub const fn overflowing_mul(self, other: Self) -> (Self, bool) {
+        self.mul_overflowing(other)
+    }
+
+    /// Multiplies keeping only the last MAX_SIZE digits,
+    /// the flag is set if any nonzero digit was dropped
+    const fn mul_overflowing(self, other: Self) -> (Self, bool) {
+        // multiplying by -1 or a power of ten (which includes 1 = 10^0) only moves digits around
+        if self.is_negative_one() {
+            return (other.neg(), false);
+        }
+        if other.is_negative_one() {
+            return (self.neg(), false);
+        }
+        if self.is_power_of_ten() && self.current_size - 1 + other.current_size <= MAX_SIZE {
+            return (other.shl_pow10(self.current_size - 1), false);
+        }
+        if other.is_power_of_ten() && other.current_size - 1 + self.current_size <= MAX_SIZE {
+            return (self.shl_pow10(other.current_size - 1), false);
+        }
+
+        // Karatsuba needs room for its intermediate sums,
+        // which is there whenever the product can't overflow
+        if self.current_size > Self::KARATSUBA_THRESHOLD
+            && other.current_size > Self::KARATSUBA_THRESHOLD
+            && self.current_size + other.current_size <= MAX_SIZE
+        {
+            let mut result = self.abs().karatsuba(other.abs());
+            result.is_negative = self.is_negative ^ other.is_negative;
+            return (result.normalize(), false);
+        }
+        let (result, overflow) = self.mul_schoolbook(other);
+        (result.normalize(), overflow)
+    }
+
+    /// Karatsuba multiplication of non-negative numbers,
+    /// the product must fit into MAX_SIZE digits
+    const fn karatsuba(self, other: Self) -> Self {
+        if self.current_size <= Self::KARATSUBA_THRESHOLD || other.current_size <= Self::KARATSUBA_THRESHOLD {
+            return self.mul_schoolbook(other).0;
+        }
+
+        // self = a1 * 10^m + a0, other = b1 * 10^m + b0
+        let m = const_helpers::max!(self.current_size, other.current_size) / 2;
+        let (a1, a0) = self.split_at(m);
+        let (b1, b0) = other.split_at(m);
+
+        let z0 = a0.karatsuba(b0);
+        let z2 = a1.karatsuba(b1);
+        let z1 = a0.add(a1).karatsuba(b0.add(b1)).sub(z0).sub(z2);
+
+        z2.shl_pow10(2 * m).add(z1.shl_pow10(m)).add(z0)
+    }
+
+    /// a.split_at(place) returns (|a| / 10^place, |a| % 10^place) by slicing the digits,
+    /// both parts are non-negative since this splits the magnitude
+    pub const fn split_at(self, place: usize) -> (Self, Self) {
+        if place >= self.current_size {
+            return (Self::DEFAULT, self.abs());
+        }
+
+        let mut high = Self::DEFAULT;
+        let mut low = Self::DEFAULT;
+        let mut i = MAX_SIZE - place;
+        while i < MAX_SIZE {
+            low.digits[i] = self.digits[i];
+            i += 1;
+        }
+        low.current_size = const_helpers::max!(place, 1);
+        low = low.trimmed();
+
+        let mut i = MAX_SIZE - self.current_size;
+        while i < MAX_SIZE - place {
+            high.digits[i + place] = self.digits[i];
+            i += 1;
+        }
+        high.current_size = self.current_size - place;
+        (high, low)
+    }
+
+    /// Writes the sign and then body, padded to the formatter width.
+    /// len is the number of characters body writes
+    fn write_padded(
+        &self,
+        f: &mut core::fmt::Formatter<'_>,
+        len: usize,
+        body: impl FnOnce(&mut core::fmt::Formatter<'_>) -> core::fmt::Result,
+    ) -> core::fmt::Result {
+        use core::fmt::Write;
+        let sign = if self.is_negative {
+            "-"
+        } else if f.sign_plus() {
+            "+"
+        } else {
+            ""
+        };
+
+        let padding = f.width().unwrap_or(0).saturating_sub(sign.len() + len);
+        if padding == 0 {
+            f.write_str(sign)?;
+            return body(f);
+        }
+
+        // like primitives, zero padding goes between the sign and the digits
+        if f.sign_aware_zero_pad() {
+            f.write_str(sign)?;
+            for _ in 0..padding {
+                f.write_char('0')?;
+            }
+            return body(f);
+        }
+
+        let (before, after) = match f.align() {
+            Some(core::fmt::Alignment::Left) => (0, padding),
+            Some(core::fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+            Some(core::fmt::Alignment::Right) | None => (padding, 0),
+        };
+        let fill = f.fill();
+        for _ in 0..before {
+            f.write_char(fill)?;
+        }
+        f.write_str(sign)?;
+        body(f)?;
+        for _ in 0..after {
+            f.write_char(fill)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the number as d.ddde<exponent>. Without a precision every significant
+    /// digit is kept, with one the mantissa is rounded half to even to that many places
+    /// like the primitive integers do
+    fn write_exp(&self, f: &mut core::fmt::Formatter<'_>, marker: char) -> core::fmt::Result {
+        use core::fmt::Write;
+        let top = MAX_SIZE - self.current_size;
+        let mut digits = self.digits;
+        let mut exponent = self.current_size - 1;
+
+        let kept = match f.precision() {
+            Some(precision) => precision,
+            None => {
+                let mut kept = exponent;
+                while kept > 0 && digits[top + kept] == 0 {
+                    kept -= 1;
+                }
+                kept
+            }
+        };
+
+        // a tie is exactly 5 followed by zeros, it rounds towards the even digit
+        let mut round_up = false;
+        if kept < exponent {
+            let next = digits[top + kept + 1];
+            let mut is_tie = next == 5;
+            let mut i = top + kept + 2;
+            while is_tie && i < MAX_SIZE {
+                is_tie = digits[i] == 0;
+                i += 1;
+            }
+            round_up = next > 5 || (next == 5 && (!is_tie || digits[top + kept] % 2 == 1));
+        }
+
+        // rounding up can carry out of the leading digit, e.g. 9.99 -> 10.0
+        let mut carried_out = false;
+        if round_up {
+            let mut i = top + kept;
+            loop {
+                if digits[i] < 9 {
+                    digits[i] += 1;
+                    break;
+                }
+                digits[i] = 0;
+                if i == top {
+                    carried_out = true;
+                    exponent += 1;
+                    break;
+                }
+                i -= 1;
+            }
+        }
+
+        let mut exponent_len = 1;
+        let mut rest = exponent / 10;
+        while rest > 0 {
+            exponent_len += 1;
+            rest /= 10;
+        }
+        let len = 1 + if kept > 0 { kept + 1 } else { 0 } + 1 + exponent_len;
+
+        self.write_padded(f, len, |f| {
+            let leading = if carried_out { 1 } else { digits[top] };
+            f.write_char((b'0' + leading) as char)?;
+            if kept > 0 {
+                f.write_char('.')?;
+            }
+            for j in 1..=kept {
+                let digit = if !carried_out && j < self.current_size { digits[top + j] } else { 0 };
+                f.write_char((b'0' + digit) as char)?;
+            }
+            f.write_char(marker)?;
+            write!(f, "{}", exponent)
+        })
+    }
+
+    /// Writes the significant digits in chunks through a small stack buffer,
+    /// with a comma every three places in alternate mode
+    fn write_digits(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let grouped = f.alternate();
+        let mut buffer = [0u8; 64];
+        let mut len = 0;
+        let mut i = MAX_SIZE - self.current_size;
+        while i < MAX_SIZE {
+            // leave room for a comma and a digit
+            if len + 2 > buffer.len() {
+                f.write_str(core::str::from_utf8(&buffer[..len]).map_err(|_| core::fmt::Error)?)?;
+                len = 0;
+            }
+            let remaining = MAX_SIZE - i;
+            if grouped && remaining < self.current_size && remaining % 3 == 0 {
+                buffer[len] = b',';
+                len += 1;
+            }
+            buffer[len] = self.digits[i] + b'0';
+            len += 1;
+            i += 1;
+        }
+        f.write_str(core::str::from_utf8(&buffer[..len]).map_err(|_| core::fmt::Error)?)
+    }
+
+    /// Returns the number of characters written by write_digits
+    fn digits_len(&self, grouped: bool) -> usize {
+        if grouped {
+            self.current_size + (self.current_size - 1) / 3
+        } else {
+            self.current_size
+        }
+    }
+
+    /// a.digit_at(place) returns the decimal digit of |a| at place, 0 is the least significant,
+    /// places past the most significant digit are 0
+    pub const fn digit_at(&self, place: usize) -> u8 {
+        if place < self.current_size { self.digits[MAX_SIZE - 1 - place] } else { 0 }
+    }
+
+    /// a.with_digit(place, value) returns a with the digit at place set to value,
+    /// growing or shrinking the size if the leading digit changes
+    pub const fn with_digit(self, place: usize, value: u8) -> Self {
+        assert!(value <= 9, "invalid digit in with_digit");
+        assert!(place < MAX_SIZE, "number too large for BigInt<MAX_SIZE>");
+        let mut result = self;
+        result.digits[MAX_SIZE - 1 - place] = value;
+        if place >= result.current_size {
+            if value == 0 {
+                return self;
+            }
+            result.current_size = place + 1;
+        }
+        result.trimmed().normalize()
+    }
+
+    /// Clears the sign of zero, so that there is no -0
+    const fn normalize(self) -> Self {
+        let mut result = self;
+        if result.is_zero() {
+            result.is_negative = false;
+        }
+        result
+    }
+
+    /// Drops leading zeros from current_size
+    const fn trimmed(self) -> Self {
+        let mut result = self;
+        while result.current_size > 1 && result.digits[MAX_SIZE - result.current_size] == 0 {
+            result.current_size -= 1;
+        }
+        result
+    }
+
+    /// Schoolbook multiplication, keeping only the last MAX_SIZE digits
+    const fn mul_schoolbook(self, other: Self) -> (Self, bool) {
         // check if either of the numbers is zero
         if self.is_zero() || other.is_zero() {
-            return Self::DEFAULT;
+            return (Self::DEFAULT, false);
         }
 
         let mut result = Self::DEFAULT;
+        let mut overflow = false;
         let mut carry = 0;
-        let mut i = MAX_SIZE - 1;
-        while i > MAX_SIZE - other.current_size - 1 {
-            let mut j = MAX_SIZE - 1;
-            while j > MAX_SIZE - self.current_size - 1 {
-                let loc = i + j - MAX_SIZE + 1;
+        let mut i = MAX_SIZE;
+        while i > MAX_SIZE - other.current_size {
+            i -= 1;
+            // digits of self below lowest would land outside of the buffer
+            let lowest = const_helpers::max!(MAX_SIZE - self.current_size, MAX_SIZE - 1 - i);
+            if lowest > MAX_SIZE - self.current_size && other.digits[i] != 0 {
+                overflow = true;
+            }
+
+            let mut j = MAX_SIZE;
+            while j > lowest {
+                j -= 1;
+                let loc = i + j + 1 - MAX_SIZE;
                 let mul = self.digits[j] as i16 * other.digits[i] as i16 + carry + result.digits[loc] as i16;
                 result.digits[loc] = (mul % 10) as u8;
                 carry = mul / 10;
-                j -= 1;
             }
-            result.digits[i + j - MAX_SIZE + 1] += carry as u8;
+            if carry > 0 {
+                if i + j < MAX_SIZE {
+                    overflow = true;
+                } else {
+                    result.digits[i + j - MAX_SIZE] += carry as u8;
+                }
+            }
             carry = 0;
-            i -= 1;
         }
 
-        let current_size = self.current_size +  other.current_size;
+        let current_size = const_helpers::min!(self.current_size + other.current_size, MAX_SIZE);
         // check if the real length is less than current_size
         let mut i = MAX_SIZE - current_size;
         while i < MAX_SIZE {
@@ -356,7 +1352,46 @@ impl<const MAX_SIZE: usize> BigInt<MAX_SIZE> {
             i += 1;
         }
         result.is_negative = self.is_negative ^ other.is_negative;
-        result
+        (result, overflow)
+    }
+
+    /// Computes |a| * factor + addend for a nonzero factor, keeping the sign of a and
+    /// the last MAX_SIZE digits, the flag is set if the carry out of the buffer was dropped
+    const fn mul_add_small(self, factor: u32, addend: u32) -> (Self, bool) {
+        let mut result = self;
+        let mut carry = addend as u64;
+        let mut i = MAX_SIZE;
+        while i > 0 && (i > MAX_SIZE - self.current_size || carry > 0) {
+            i -= 1;
+            let current = self.digits[i] as u64 * factor as u64 + carry;
+            result.digits[i] = (current % 10) as u8;
+            carry = current / 10;
+        }
+        // the last digit written is nonzero unless the result is zero
+        result.current_size = MAX_SIZE - i;
+        (result, carry > 0)
+    }
+
+    /// a.div_rem_small(d) divides by a small divisor in a single pass, returning the quotient
+    /// rounded towards zero like div and the remainder of |a| / d, panics if d is zero
+    pub const fn div_rem_small(self, divisor: u32) -> (Self, u32) {
+        assert!(divisor != 0, "division by zero");
+        let divisor = divisor as u64;
+        let mut quotient = Self::DEFAULT;
+        let mut remainder = 0u64;
+        let mut i = MAX_SIZE - self.current_size;
+        while i < MAX_SIZE {
+            let current = remainder * 10 + self.digits[i] as u64;
+            quotient.digits[i] = (current / divisor) as u8;
+            remainder = current % divisor;
+            i += 1;
+        }
+        quotient.current_size = self.current_size;
+        while quotient.current_size > 1 && quotient.digits[MAX_SIZE - quotient.current_size] == 0 {
+            quotient.current_size -= 1;
+        }
+        quotient.is_negative = self.is_negative && !quotient.is_zero();
+        (quotient, remainder as u32)
     }
 
     // self / other
@@ -371,392 +1406,3559 @@ impl<const MAX_SIZE: usize> BigInt<MAX_SIZE> {
         }
 
 
-        let mut divident = self.const_clone();
-        let len = other.current_size;
-        let mut shift = 0;
+        let n = other.current_size;
+        if self.abs_less(other) {
+            remainder = self;
+        } else if n == 1 {
+            // short division by a single digit
+            let divisor = other.digits[MAX_SIZE - 1] as u16;
+            let mut carry = 0u16;
+            let mut i = MAX_SIZE - self.current_size;
+            while i < MAX_SIZE {
+                let current = carry * 10 + self.digits[i] as u16;
+                quotient.digits[i] = (current / divisor) as u8;
+                carry = current % divisor;
+                i += 1;
+            }
+            quotient.current_size = self.current_size;
+            remainder.digits[MAX_SIZE - 1] = carry as u8;
+        } else {
+            // long division, the remainder starts as the dividend and shrinks
+            // by q * other << j for every quotient digit q at place j
+            remainder = self;
+            let m = self.current_size - n;
+            let top = other.digits[MAX_SIZE - n] as i16 * 10 + other.digits[MAX_SIZE - n + 1] as i16;
 
-        'outer: loop {
-            let mut num = 0u8;
-            'inner: loop {
-                let lhs_pos = MAX_SIZE + shift - divident.current_size;
-                let rhs_pos = MAX_SIZE - other.current_size;
-                
-                if lhs_pos + len - 1 >= MAX_SIZE && lhs_pos + len - 1 >= MAX_SIZE {
-                    break 'outer;
-                }
+            let mut j = m + 1;
+            while j > 0 {
+                j -= 1;
+                // estimating from three digits of the remainder and two of the divisor
+                // is never too small and at most one too large, so the divisor
+                // doesn't need to be normalized as in Knuth's Algorithm D
+                let window = remainder.digit_at(j + n) as i16 * 100
+                    + remainder.digit_at(j + n - 1) as i16 * 10
+                    + remainder.digit_at(j + n - 2) as i16;
+                let mut q = const_helpers::min!(window / top, 9);
 
-                // compare the two numbers
-                let mut pos = 0;
-                if MAX_SIZE + shift < divident.current_size + 1 || divident.digits[MAX_SIZE + shift - divident.current_size - 1] == 0 {
-                    while pos < len {
-                        if divident.digits[lhs_pos + pos] > other.digits[rhs_pos + pos] {
-                            break; // divident is greater - subtract
-                        } else if divident.digits[lhs_pos + pos] < other.digits[rhs_pos + pos] {
-                            break 'inner; // divident is smaller - shift
+                if q > 0 {
+                    // subtract q * other
+                    let mut carry = 0;
+                    let mut borrow = 0;
+                    let mut k = 0;
+                    while k < n {
+                        let product = q * other.digits[MAX_SIZE - 1 - k] as i16 + carry;
+                        carry = product / 10;
+                        let mut diff = remainder.digits[MAX_SIZE - 1 - j - k] as i16 - product % 10 - borrow;
+                        borrow = 0;
+                        if diff < 0 {
+                            diff += 10;
+                            borrow = 1;
                         }
-                        pos += 1;
+                        remainder.digits[MAX_SIZE - 1 - j - k] = diff as u8;
+                        k += 1;
                     }
-                }
 
+                    // the estimate was one too large, add other back
+                    if (remainder.digit_at(j + n) as i16) < carry + borrow {
+                        q -= 1;
+                        let mut carry = 0;
+                        let mut k = 0;
+                        while k < n {
+                            let sum = remainder.digits[MAX_SIZE - 1 - j - k] + other.digits[MAX_SIZE - 1 - k] + carry;
+                            remainder.digits[MAX_SIZE - 1 - j - k] = sum % 10;
+                            carry = sum / 10;
+                            k += 1;
+                        }
+                    }
 
-                // subtract
-                let mut pos = 0;
-                let mut borrow = 0;
-                let lhs_pos = MAX_SIZE - divident.current_size + shift + len - 1;
-                let rhs_pos = MAX_SIZE - other.current_size + len - 1;
-                while pos < len {
-                    let diff = 10 + divident.digits[lhs_pos - pos] as i16 - other.digits[rhs_pos - pos] as i16 - borrow;
-                    divident.digits[lhs_pos - pos] = (diff % 10) as u8;
-                    borrow = (diff / 10) ^ 1;
-                    pos += 1;
+                    // what is left is less than other, so it fits into n digits
+                    if j + n < MAX_SIZE {
+                        remainder.digits[MAX_SIZE - 1 - j - n] = 0;
+                    }
                 }
+                quotient.digits[MAX_SIZE - 1 - j] = q as u8;
+            }
+            quotient.current_size = m + 1;
+            remainder.current_size = n;
+        }
+        quotient = quotient.trimmed();
+        remainder = remainder.trimmed();
 
-                // subtract the borrow
-                if lhs_pos >= pos {
-                    assert!(divident.digits[lhs_pos - pos] as i16 >= borrow, "borrow is greater than the digit");
-                    divident.digits[lhs_pos - pos] -= borrow as u8;
-                } else {
-                    assert!(borrow == 0, "borrow is not zero");
-                }
+        // truncated division: the remainder takes the sign of the dividend
+        // so that self == quotient * other + remainder, like % on primitives
+        quotient.is_negative = self.is_negative ^ other.is_negative;
+        remainder.is_negative = self.is_negative;
 
-                num += 1;
-                
-                
-            }
-            quotient.digits[shift] = num;
-            shift += 1;
+        quotient.current_size = const_helpers::max!(quotient.size(), 1);
 
-        }
+        (quotient.normalize(), remainder.normalize())
+    }
 
-        // move digits to the right
-        let mut i = 1;
-        while i <= shift {
-            let value = quotient.digits[shift - i];
-            quotient.digits[shift - i] = 0;
-            quotient.digits[MAX_SIZE - i] = value;
-            i += 1;
+    /// a.checked_div(b) returns None instead of panicking if b is zero
+    pub const fn checked_div(self, other: Self) -> Option<(Self, Self)> {
+        if other.is_zero() {
+            return None;
         }
-        quotient.current_size = shift;
+        Some(self.div(other))
+    }
 
-        // check if the real length is less than current_size
-        let mut i = MAX_SIZE - shift;
-        while i < MAX_SIZE {
-            if quotient.digits[i] != 0 {
-                quotient.current_size = MAX_SIZE - i;
-                break;
+    /// a.pow(n) returns a^n using exponentiation by squaring
+    pub const fn pow(self, exp: u32) -> Self {
+        let mut result = Self::from_i128(1);
+        let mut base = self;
+        let mut exp = exp;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.mul(base);
+            }
+            exp >>= 1;
+            // don't square once more than needed, it may not fit into MAX_SIZE
+            if exp > 0 {
+                base = base.mul(base);
             }
-            i += 1;
         }
+        result
+    }
 
-        // copy the remainder
-        let mut i = MAX_SIZE - divident.current_size;
-        while i < MAX_SIZE {
-            remainder.digits[i] = divident.digits[i];
-            i += 1;
+    /// a.pow_mod(e, m) returns a^e mod |m|, the result is always in [0, |m|)
+    pub const fn pow_mod(self, exp: Self, modulus: Self) -> Self {
+        assert!(!exp.is_negative, "negative exponent");
+        let modulus = modulus.abs();
+
+        // reduce everything first so that intermediate products
+        // never need more than 2 * modulus.size() digits
+        let two = Self::from_i128(2);
+        let mut result = Self::from_i128(1).div(modulus).1;
+        let mut base = self.div(modulus).1;
+        if base.is_negative {
+            base = base.add(modulus);
         }
 
-        // check if the real length is less than current_size
-        let mut i = MAX_SIZE - divident.current_size;
-        while i < MAX_SIZE {
-            if remainder.digits[i] != 0 {
-                remainder.current_size = MAX_SIZE - i;
-                break;
+        let mut exp = exp;
+        while !exp.is_zero() {
+            if !exp.is_even() {
+                result = result.mul(base).div(modulus).1;
+            }
+            exp = exp.div(two).0;
+            if !exp.is_zero() {
+                base = base.mul(base).div(modulus).1;
             }
-            i += 1;
         }
+        result
+    }
 
-        quotient.is_negative = self.is_negative ^ other.is_negative;
-        remainder.is_negative = self.is_negative | (self.is_negative && other.is_negative);
+    /// a.gcd(b) returns the greatest common divisor of |a| and |b|
+    pub const fn gcd(self, other: Self) -> Self {
+        let mut a = self.abs();
+        let mut b = other.abs();
+        while !b.is_zero() {
+            let remainder = a.div(b).1;
+            a = b;
+            b = remainder;
+        }
+        a
+    }
 
-        quotient.current_size = const_helpers::max!(quotient.size(), 1);
+    /// a.lcm(b) returns the least common multiple of |a| and |b|
+    pub const fn lcm(self, other: Self) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return Self::DEFAULT;
+        }
+        // divide first to keep the intermediate value small
+        self.div(self.gcd(other)).0.mul(other).abs()
+    }
 
-        if quotient.is_zero() {
-            quotient.is_negative = false;
+    /// a.isqrt() returns floor(sqrt(a)), panics if a is negative
+    pub const fn isqrt(self) -> Self {
+        assert!(!self.is_negative, "square root of a negative number");
+        if self.is_zero() {
+            return Self::DEFAULT;
         }
 
-        if remainder.is_zero() {
-            remainder.is_negative = false;
+        // start from a power of ten that is not less than the root,
+        // or from a itself when that power doesn't fit
+        let mut x = self;
+        let half = self.current_size.div_ceil(2);
+        if half < MAX_SIZE {
+            x = Self::DEFAULT;
+            x.digits[MAX_SIZE - 1 - half] = 1;
+            x.current_size = half + 1;
         }
 
-        (quotient, remainder)
+        // Newton's iteration decreases monotonically towards the root,
+        // (x + a / x) / 2 is computed as q + (x - q) / 2 so it can't overflow
+        let two = Self::from_i128(2);
+        loop {
+            let quotient = self.div(x).0;
+            let next = quotient.add(x.sub(quotient).div(two).0);
+            if !next.less(x) {
+                return x;
+            }
+            x = next;
+        }
     }
-    
-}
 
-pub mod const_helpers {
-    #[macro_export]
-    macro_rules! max {
-        ($x:expr, $y:expr) => {
-            if $x > $y { $x } else { $y }
-        };
+    /// a.neg() returns -a, zero is never negative
+    pub const fn neg(self) -> Self {
+        let mut result = self;
+        result.is_negative = !self.is_negative && !self.is_zero();
+        result
     }
 
-    #[macro_export]
-    macro_rules! min {
-        ($x:expr, $y:expr) => {
-            if $x < $y { $x } else { $y }
-        };
+    /// a.abs() returns |a|
+    pub const fn abs(self) -> Self {
+        let mut result = self;
+        result.is_negative = false;
+        result
     }
 
-    pub(crate) use max;
-    #[allow(unused_imports)]
-    pub(crate) use min;
-}
-
-
+    /// a.div_euclid(b) returns q such that a == q * b + r with 0 <= r < |b|
+    pub const fn div_euclid(self, other: Self) -> Self {
+        let (quotient, remainder) = self.div(other);
+        if !remainder.is_negative {
+            return quotient;
+        }
+        if other.is_negative {
+            quotient.add(Self::from_i128(1))
+        } else {
+            quotient.sub(Self::from_i128(1))
+        }
+    }
 
-// TESTS
-mod tests {
-    use crate::bigint::BigInt;
-    #[allow(dead_code)]
-    type BigIntTest = BigInt<100>;
+    /// a.rem_euclid(b) returns r such that a == q * b + r with 0 <= r < |b|
+    pub const fn rem_euclid(self, other: Self) -> Self {
+        let remainder = self.div(other).1;
+        if remainder.is_negative {
+            remainder.add(other.abs())
+        } else {
+            remainder
+        }
+    }
 
-    #[test]
-    fn from_positive() {
-        const STR: &str = "123456789123456789123456789123456789";
-        const X: BigIntTest = BigInt::from_str(STR);
-        const DIGITS: [u8; X.max_size()] = { 
-            let mut result =  [0u8; X.max_size()]; 
+    /// a.signum() returns -1, 0 or 1 depending on the sign of a
+    pub const fn signum(self) -> Self {
+        if self.is_zero() {
+            Self::DEFAULT
+        } else if self.is_negative {
+            Self::from_i128(-1)
+        } else {
+            Self::from_i128(1)
+        }
+    }
 
-            let digits = BigIntTest::DEFAULT.max_size() - STR.len();
-            let mut i = digits;
-            while i < BigIntTest::DEFAULT.max_size() {
-                result[i as usize] = ((i - digits) % 9 + 1) as u8;
-                i+=1;
-            }
+    /// a.min(b) returns the smaller of the two, or a if they are equal
+    pub const fn min(self, other: Self) -> Self {
+        if other.less(self) { other } else { self }
+    }
 
-            result
-        };
+    /// a.max(b) returns the greater of the two, or a if they are equal
+    pub const fn max(self, other: Self) -> Self {
+        if self.less(other) { other } else { self }
+    }
 
-        const EXPECTED: BigIntTest = BigInt { is_negative: false, digits:DIGITS, current_size: STR.len()}; 
-        assert_eq!(X, EXPECTED);
+    /// a.clamp(lo, hi) restricts a to [lo, hi], panics if lo > hi
+    pub const fn clamp(self, lo: Self, hi: Self) -> Self {
+        assert!(!hi.less(lo), "clamp: lo is greater than hi");
+        self.max(lo).min(hi)
+    }
+
+    /// a.shl_pow10(n) returns a * 10^n by appending n zero digits
+    pub const fn shl_pow10(self, n: usize) -> Self {
+        if self.is_zero() || n == 0 {
+            return self;
+        }
+        assert!(self.current_size + n <= MAX_SIZE, "shift overflow");
+
+        let mut result = Self::DEFAULT;
+        let mut i = MAX_SIZE - self.current_size;
+        while i < MAX_SIZE {
+            result.digits[i - n] = self.digits[i];
+            i += 1;
+        }
+        result.current_size = self.current_size + n;
+        result.is_negative = self.is_negative;
+        result
+    }
+
+    /// a.shr_pow10(n) drops the last n digits,
+    /// which is a / 10^n rounded towards zero like div
+    pub const fn shr_pow10(self, n: usize) -> Self {
+        if n >= self.current_size {
+            return Self::DEFAULT;
+        }
+
+        let mut result = Self::DEFAULT;
+        let mut i = MAX_SIZE - self.current_size;
+        while i < MAX_SIZE - n {
+            result.digits[i + n] = self.digits[i];
+            i += 1;
+        }
+        result.current_size = self.current_size - n;
+        result.is_negative = self.is_negative;
+        result
+    }
+
+    /// a.ilog10() returns floor(log10(a)), panics if a is not positive
+    pub const fn ilog10(self) -> u32 {
+        assert!(!self.is_negative && !self.is_zero(), "argument of integer logarithm must be positive");
+        (self.current_size - 1) as u32
+    }
+
+    /// a.ilog2() returns floor(log2(a)), panics if a is not positive
+    pub const fn ilog2(self) -> u32 {
+        assert!(!self.is_negative && !self.is_zero(), "argument of integer logarithm must be positive");
+        let two = Self::from_i128(2);
+        let mut value = self;
+        let mut result = 0;
+        while value.current_size > 1 || value.digits[MAX_SIZE - 1] > 1 {
+            value = value.div(two).0;
+            result += 1;
+        }
+        result
+    }
+
+    /// a.resize::<NEW>() returns a as a BigInt<NEW>, panicking if it has more than NEW digits
+    pub const fn resize<const NEW: usize>(self) -> BigInt<NEW> {
+        match self.try_resize() {
+            Some(result) => result,
+            None => panic!("number too large for the new size"),
+        }
+    }
+
+    /// a.try_resize::<NEW>() returns None if a has more than NEW digits
+    pub const fn try_resize<const NEW: usize>(self) -> Option<BigInt<NEW>> {
+        if self.current_size > NEW {
+            return None;
+        }
+        let mut result = BigInt::<NEW>::DEFAULT;
+        result.is_negative = self.is_negative;
+        result.current_size = self.current_size;
+        let mut i = 0;
+        while i < self.current_size {
+            result.digits[NEW - 1 - i] = self.digits[MAX_SIZE - 1 - i];
+            i += 1;
+        }
+        Some(result)
+    }
+
+    /// a.is_probable_prime(rounds, rng) runs Miller-Rabin with rounds witnesses taken from rng,
+    /// a prime always passes and a composite passes with probability at most 4^-rounds.
+    /// Like pow_mod it needs room for 2 * a.size() digits
+    pub fn is_probable_prime(&self, rounds: u32, rng: &mut impl FnMut() -> Self) -> bool {
+        let one = Self::from_i128(1);
+        let two = Self::from_i128(2);
+        let n = *self;
+        if n.less(two) {
+            return false;
+        }
+        if n.less(Self::from_i128(4)) {
+            return true;
+        }
+        if n.is_even() {
+            return false;
+        }
+
+        // n - 1 = d * 2^s with d odd
+        let n_minus_one = n.sub(one);
+        let mut d = n_minus_one;
+        let mut s = 0;
+        while d.is_even() {
+            d = d.div(two).0;
+            s += 1;
+        }
+
+        'witness: for _ in 0..rounds {
+            // a witness in [2, n - 2]
+            let a = rng().rem_euclid(n.sub(Self::from_i128(3))).add(two);
+            let mut x = a.pow_mod(d, n);
+            if x.equal(one) || x.equal(n_minus_one) {
+                continue;
+            }
+            for _ in 1..s {
+                x = x.mul(x).div(n).1;
+                if x.equal(n_minus_one) {
+                    continue 'witness;
+                }
+            }
+            return false;
+        }
+        true
+    }
+
+    /// a.is_prime_trial() checks primality exactly by trial division up to isqrt(a),
+    /// which is only practical for small values
+    pub const fn is_prime_trial(self) -> bool {
+        let two = Self::from_i128(2);
+        if self.less(two) {
+            return false;
+        }
+        if self.is_even() {
+            return self.equal(two);
+        }
+
+        let limit = self.isqrt();
+        let mut divisor = Self::from_i128(3);
+        while !limit.less(divisor) {
+            if self.div(divisor).1.is_zero() {
+                return false;
+            }
+            divisor = divisor.add(two);
+        }
+        true
+    }
+
+    /// BigInt::choose(n, k) returns the binomial coefficient C(n, k), zero if k > n
+    pub const fn choose(n: u32, k: u32) -> Self {
+        if k > n {
+            return Self::DEFAULT;
+        }
+        let k = const_helpers::min!(k, n - k);
+
+        // after step i the result is C(n, i + 1) = C(n, i) * (n - i) / (i + 1). Dividing
+        // C(n, i) by g = gcd(C(n, i), i + 1) first leaves (i + 1) / g dividing n - i,
+        // so no intermediate value is larger than the result
+        let mut result = Self::from_i128(1);
+        let mut i = 0;
+        while i < k {
+            let (mut g, mut r) = (i + 1, result.div_rem_small(i + 1).1);
+            while r != 0 {
+                (g, r) = (r, g % r);
+            }
+            let factor = (n - i) / ((i + 1) / g);
+            result = result.div_rem_small(g).0.mul(Self::from_i128(factor as i128));
+            i += 1;
+        }
+        result
+    }
+
+    /// BigInt::fibonacci(n) returns F(n) with F(0) = 0, F(1) = 1, using fast doubling:
+    /// F(2k) = F(k) * (2F(k + 1) - F(k)) and F(2k + 1) = F(k)^2 + F(k + 1)^2
+    pub const fn fibonacci(n: u32) -> Self {
+        // (a, b) = (F(k), F(k + 1)) for k = the bits of n seen so far
+        let mut a = Self::DEFAULT;
+        let mut b = Self::from_i128(1);
+        let mut bit = u32::BITS;
+        while bit > 1 {
+            bit -= 1;
+            let c = a.mul(b.sub(a).add(b));
+            let d = a.mul(a).add(b.mul(b));
+            if (n >> bit) & 1 == 0 {
+                (a, b) = (c, d);
+            } else {
+                (a, b) = (d, c.add(d));
+            }
+        }
+        // the last bit only computes F(n) itself, F(n + 1) may not fit
+        if n & 1 == 0 {
+            a.mul(b.sub(a).add(b))
+        } else {
+            a.mul(a).add(b.mul(b))
+        }
+    }
+
+    /// a.as_significant_slice() returns the significant digits of |a| as values 0..=9,
+    /// most significant first, without copying them
+    pub const fn as_significant_slice(&self) -> &[u8] {
+        self.digits.split_at(MAX_SIZE - self.current_size).1
+    }
+
+    /// a.digits() iterates over the significant digits of |a|, most significant first
+    pub fn digits(&self) -> impl DoubleEndedIterator<Item = u8> + ExactSizeIterator + '_ {
+        self.as_significant_slice().iter().copied()
+    }
+
+    /// a.digits_le() iterates over the significant digits of |a|, least significant first
+    pub fn digits_le(&self) -> impl DoubleEndedIterator<Item = u8> + ExactSizeIterator + '_ {
+        self.digits().rev()
+    }
+
+    /// a.mod_inverse(m) returns x in [0, |m|) with a * x == 1 mod m,
+    /// or None if a and m aren't coprime
+    pub const fn mod_inverse(self, modulus: Self) -> Option<Self> {
+        let modulus = modulus.abs();
+
+        // extended Euclid, keeping old_s * a == old_r mod m
+        let mut old_r = self.rem_euclid(modulus);
+        let mut r = modulus;
+        let mut old_s = Self::from_i128(1);
+        let mut s = Self::DEFAULT;
+        while !r.is_zero() {
+            let q = old_r.div(r).0;
+            (old_r, r) = (r, old_r.sub(q.mul(r)));
+            (old_s, s) = (s, old_s.sub(q.mul(s)));
+        }
+
+        if old_r.equal(Self::from_i128(1)) {
+            Some(old_s.rem_euclid(modulus))
+        } else {
+            None
+        }
+    }
+
+    /// a.is_power_of_ten() returns true if a == 10^n for some n >= 0
+    pub const fn is_power_of_ten(&self) -> bool {
+        if self.is_negative || self.digits[MAX_SIZE - self.current_size] != 1 {
+            return false;
+        }
+        let mut i = MAX_SIZE - self.current_size + 1;
+        while i < MAX_SIZE {
+            if self.digits[i] != 0 {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// a.next_power_of_ten() returns the smallest power of ten >= a, which is 1 for a <= 1
+    pub const fn next_power_of_ten(self) -> Self {
+        if self.is_power_of_ten() {
+            return self;
+        }
+        if self.is_negative || self.is_zero() {
+            return Self::from_i128(1);
+        }
+        Self::pow10(self.current_size)
+    }
+
+    /// BigInt::random_digits(n, rng) returns a random non-negative number with exactly n digits,
+    /// rng must return digits in 0..=9 and is called again while the leading digit would be 0
+    pub fn random_digits(num_digits: usize, rng: &mut impl FnMut() -> u8) -> Self {
+        assert!(num_digits >= 1 && num_digits <= MAX_SIZE, "number of digits out of range");
+        let mut next_digit = || {
+            let digit = rng();
+            assert!(digit <= 9, "rng returned an invalid digit");
+            digit
+        };
+
+        let mut result = Self::DEFAULT;
+        result.current_size = num_digits;
+        let top = MAX_SIZE - num_digits;
+        result.digits[top] = loop {
+            let digit = next_digit();
+            if digit != 0 || num_digits == 1 {
+                break digit;
+            }
+        };
+        for digit in &mut result.digits[top + 1..] {
+            *digit = next_digit();
+        }
+        result
+    }
+
+    /// BigInt::random_below(bound, rng) returns a uniformly random number in [0, bound),
+    /// drawing bound.size() digits from rng until they form a number below bound
+    pub fn random_below(bound: Self, rng: &mut impl FnMut() -> u8) -> Self {
+        assert!(!bound.is_negative && !bound.is_zero(), "bound must be positive");
+        let top = MAX_SIZE - bound.current_size;
+        loop {
+            let mut result = Self { current_size: bound.current_size, ..Self::DEFAULT };
+            for digit in &mut result.digits[top..] {
+                *digit = rng();
+                assert!(*digit <= 9, "rng returned an invalid digit");
+            }
+            let result = result.trimmed();
+            if result.less(bound) {
+                return result;
+            }
+        }
+    }
+
+    /// a.trailing_zeros10() returns the largest n such that 10^n divides a, 0 for a == 0
+    pub const fn trailing_zeros10(&self) -> usize {
+        if self.is_zero() {
+            return 0;
+        }
+        let mut count = 0;
+        while self.digits[MAX_SIZE - 1 - count] == 0 {
+            count += 1;
+        }
+        count
+    }
+
+    /// BigInt::reduce(num, den) returns num / den in lowest terms,
+    /// with the sign moved to the numerator so that the denominator is positive
+    pub const fn reduce(num: Self, den: Self) -> (Self, Self) {
+        assert!(!den.is_zero(), "zero denominator");
+        let divisor = num.gcd(den);
+        let mut num = num.div(divisor).0;
+        let den = den.div(divisor).0;
+        if den.is_negative {
+            num = num.neg();
+        }
+        (num, den.abs())
+    }
+
+    /// a.add_in_place(&b) sets a to a + b, adding the digits of b into a's buffer
+    /// when the signs match instead of building a new number
+    pub fn add_in_place(&mut self, other: &Self) {
+        if self.is_negative != other.is_negative {
+            *self = self.add(*other);
+            return;
+        }
+
+        let size = const_helpers::max!(self.current_size, other.current_size);
+        let mut carry = 0;
+        for (digit, other_digit) in self.digits[MAX_SIZE - size..].iter_mut().zip(&other.digits[MAX_SIZE - size..]).rev() {
+            let sum = *digit + other_digit + carry;
+            *digit = sum % 10;
+            carry = sum / 10;
+        }
+
+        self.current_size = size;
+        if carry > 0 {
+            debug_assert!(size < MAX_SIZE, "addition overflow");
+            if size < MAX_SIZE {
+                self.digits[MAX_SIZE - 1 - size] = carry;
+                self.current_size += 1;
+            } else {
+                *self = self.trimmed().normalize();
+            }
+        }
+    }
+
+    /// a.nth_root(n) returns the n-th root of a rounded towards zero,
+    /// panics if n is 0 or if a is negative and n is even
+    pub const fn nth_root(self, n: u32) -> Self {
+        assert!(n > 0, "zeroth root");
+        assert!(!self.is_negative || n % 2 == 1, "even root of a negative number");
+        if n == 1 || self.abs_less(Self::from_i128(2)) {
+            return self;
+        }
+        let value = self.abs();
+
+        // binary search keeping lo^n <= value < hi^n, where hi starts as a power
+        // of ten that is larger than the root, or MAX when that power doesn't fit
+        let one = Self::from_i128(1);
+        let two = Self::from_i128(2);
+        let mut lo = one;
+        let exp = self.current_size.div_ceil(n as usize);
+        let mut hi = if exp < MAX_SIZE { Self::pow10(exp) } else { Self::MAX };
+        while lo.add(one).less(hi) {
+            let mid = lo.add(hi.sub(lo).div(two).0);
+            if mid.pow_at_most(n, value) {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+
+        if self.is_negative { lo.neg() } else { lo }
+    }
+
+    /// a.pow_at_most(n, limit) returns a^n <= limit for a >= 2 without overflowing
+    const fn pow_at_most(self, n: u32, limit: Self) -> bool {
+        let mut power = Self::from_i128(1);
+        let mut i = 0;
+        while i < n {
+            power = match power.checked_mul(self) {
+                Some(power) => power,
+                None => return false,
+            };
+            // a >= 2, so this is reached after at most log2(limit) steps
+            if limit.less(power) {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// a.to_f64() returns the f64 closest to a, or an infinity if |a| is above f64::MAX
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_f64(&self) -> f64 {
+        // f64::MAX has 309 digits, anything longer is out of range
+        const MAX_DIGITS: usize = 309;
+        let infinity = if self.is_negative { f64::NEG_INFINITY } else { f64::INFINITY };
+        if self.current_size > MAX_DIGITS {
+            return infinity;
+        }
+
+        // every digit can matter for rounding to the nearest f64, so all of them
+        // are handed to core's parser, which rounds exactly
+        let mut buffer = [0u8; MAX_DIGITS + 1];
+        let mut len = 0;
+        if self.is_negative {
+            buffer[len] = b'-';
+            len += 1;
+        }
+        for &digit in &self.digits[MAX_SIZE - self.current_size..] {
+            buffer[len] = digit + b'0';
+            len += 1;
+        }
+        core::str::from_utf8(&buffer[..len]).ok().and_then(|s| s.parse().ok()).unwrap_or(infinity)
+    }
+
+    /// BigInt::from_f64(x) returns x truncated towards zero,
+    /// or an error if x is NaN, infinite or too large for MAX_SIZE digits
+    pub fn from_f64(x: f64) -> Result<Self, FromF64Error> {
+        if x.is_nan() {
+            return Err(FromF64Error::NaN);
+        }
+        if x.is_infinite() {
+            return Err(FromF64Error::Infinite);
+        }
+
+        // x = mantissa * 2^exponent with an integer mantissa
+        let bits = x.to_bits();
+        let biased_exponent = ((bits >> 52) & 0x7ff) as i32;
+        let fraction = bits & ((1 << 52) - 1);
+        let (mantissa, mut exponent) = if biased_exponent == 0 {
+            (fraction, -1074)
+        } else {
+            (fraction | (1 << 52), biased_exponent - 1075)
+        };
+
+        // f64::MAX has 309 digits, so every finite value fits into a BigInt<309>
+        let mut result = if exponent < 0 {
+            BigInt::<309>::from_u128(mantissa.checked_shr(exponent.unsigned_abs()).unwrap_or(0) as u128)
+        } else {
+            let mut result = BigInt::<309>::from_u128(mantissa as u128);
+            while exponent > 0 {
+                let step = const_helpers::min!(exponent, 64);
+                result = result.mul(BigInt::from_u128(1 << step));
+                exponent -= step;
+            }
+            result
+        };
+        result.is_negative = x.is_sign_negative();
+        result.normalize().try_resize().ok_or(FromF64Error::OutOfRange)
+    }
+
+    /// a.bit_length() returns the number of bits needed to write |a| in binary, 0 for zero
+    pub const fn bit_length(self) -> u32 {
+        if self.is_zero() {
+            return 0;
+        }
+        self.abs().ilog2() + 1
+    }
+
+    /// a.is_divisible_by(b) returns true if b divides a, panics if b is zero.
+    /// 2, 5 and 10 are decided by the last digit and 3 and 9 by the digit sum
+    pub const fn is_divisible_by(self, other: Self) -> bool {
+        assert!(!other.is_zero(), "division by zero");
+        if other.current_size == 1 {
+            let last = self.digits[MAX_SIZE - 1];
+            match other.digits[MAX_SIZE - 1] {
+                1 => return true,
+                2 => return last % 2 == 0,
+                5 => return last % 5 == 0,
+                3 => return self.sum_of_digits() % 3 == 0,
+                9 => return self.sum_of_digits() % 9 == 0,
+                _ => {}
+            }
+        } else if other.current_size == 2 && other.digits[MAX_SIZE - 2] == 1 && other.digits[MAX_SIZE - 1] == 0 {
+            return self.digits[MAX_SIZE - 1] == 0;
+        }
+        self.div(other).1.is_zero()
+    }
+
+    /// BigInt::powers(b) iterates over b^0, b^1, b^2, ... up to the last power that fits,
+    /// for b = -1, 0 or 1 the powers repeat and it stops before the first repeated one
+    pub fn powers(base: Self) -> impl Iterator<Item = Self> {
+        let distinct = if base.is_one() {
+            1
+        } else if base.is_zero() || base.is_negative_one() {
+            2
+        } else {
+            usize::MAX
+        };
+        core::iter::successors(Some(Self::from_i128(1)), move |power| power.checked_mul(base)).take(distinct)
+    }
+
+    /// a.isqrt_rem() returns (s, a - s^2) where s = a.isqrt(), panics if a is negative
+    pub const fn isqrt_rem(self) -> (Self, Self) {
+        let root = self.isqrt();
+        (root, self.sub(root.mul(root)))
+    }
+
+    /// a.mul_mod(b, m) returns a * b mod |m| in [0, |m|), reducing the operands first
+    /// so that the product never needs more than 2 * m.size() digits
+    pub const fn mul_mod(self, other: Self, modulus: Self) -> Self {
+        let modulus = modulus.abs();
+        let a = self.rem_euclid(modulus);
+        let b = other.rem_euclid(modulus);
+        a.mul(b).div(modulus).1
+    }
+
+    /// a.reverse_digits() returns a with its significant digits in reverse order,
+    /// keeping the sign, so 1200 becomes 21
+    pub const fn reverse_digits(self) -> Self {
+        let mut result = self;
+        let top = MAX_SIZE - self.current_size;
+        let mut i = 0;
+        while i < self.current_size {
+            result.digits[top + i] = self.digits[MAX_SIZE - 1 - i];
+            i += 1;
+        }
+        result.trimmed()
+    }
+
+    /// a.is_palindrome() returns true if the digits of |a| read the same in both directions
+    pub const fn is_palindrome(&self) -> bool {
+        let mut lo = MAX_SIZE - self.current_size;
+        let mut hi = MAX_SIZE - 1;
+        while lo < hi {
+            if self.digits[lo] != self.digits[hi] {
+                return false;
+            }
+            lo += 1;
+            hi -= 1;
+        }
+        true
+    }
+
+    /// a.widening_mul::<OUT>(b) returns the full product a * b as a BigInt<OUT>,
+    /// OUT must be at least a.size() + b.size() so that nothing is truncated
+    pub const fn widening_mul<const OUT: usize>(self, other: Self) -> BigInt<OUT> {
+        assert!(OUT >= self.current_size + other.current_size, "output too small for the product");
+        self.resize::<OUT>().mul(other.resize::<OUT>())
+    }
+
+    /// a.increment() returns a + 1
+    pub const fn increment(self) -> Self {
+        if self.is_negative {
+            self.abs_decrement()
+        } else {
+            self.abs_increment()
+        }
+    }
+
+    /// a.decrement() returns a - 1
+    pub const fn decrement(self) -> Self {
+        if self.is_negative || self.is_zero() {
+            let mut result = self.abs_increment();
+            result.is_negative = true;
+            result
+        } else {
+            self.abs_decrement()
+        }
+    }
+
+    /// Adds one to the magnitude, keeping the sign
+    const fn abs_increment(self) -> Self {
+        let mut result = self;
+        let mut i = MAX_SIZE;
+        while i > MAX_SIZE - self.current_size {
+            i -= 1;
+            if result.digits[i] < 9 {
+                result.digits[i] += 1;
+                return result;
+            }
+            result.digits[i] = 0;
+        }
+
+        // every digit was a nine
+        debug_assert!(self.current_size < MAX_SIZE, "addition overflow");
+        if self.current_size < MAX_SIZE {
+            result.digits[i - 1] = 1;
+            result.current_size += 1;
+        }
+        result.trimmed().normalize()
+    }
+
+    /// Subtracts one from a nonzero magnitude, keeping the sign
+    const fn abs_decrement(self) -> Self {
+        let mut result = self;
+        let mut i = MAX_SIZE;
+        while i > MAX_SIZE - self.current_size {
+            i -= 1;
+            if result.digits[i] > 0 {
+                result.digits[i] -= 1;
+                break;
+            }
+            result.digits[i] = 9;
+        }
+        result.trimmed().normalize()
+    }
+
+    /// a.count_digit(d) returns how many of the significant digits of |a| are d
+    pub const fn count_digit(&self, d: u8) -> usize {
+        assert!(d <= 9, "invalid digit in count_digit");
+        let mut count = 0;
+        let mut i = MAX_SIZE - self.current_size;
+        while i < MAX_SIZE {
+            if self.digits[i] == d {
+                count += 1;
+            }
+            i += 1;
+        }
+        count
+    }
+
+    /// a.rotate_left(n) cyclically moves the significant digits n places to the left,
+    /// so 123456 becomes 345612 for n = 2. The sign is kept and
+    /// leading zeros produced by the rotation are dropped, so 102 becomes 21 for n = 1
+    pub const fn rotate_left(self, n: usize) -> Self {
+        let size = self.current_size;
+        let n = n % size;
+        let top = MAX_SIZE - size;
+        let mut result = self;
+        let mut i = 0;
+        while i < size {
+            result.digits[top + i] = self.digits[top + (i + n) % size];
+            i += 1;
+        }
+        result.trimmed().normalize()
+    }
+
+    /// a.rotate_right(n) cyclically moves the significant digits n places to the right,
+    /// so 123456 becomes 561234 for n = 2, the same rules as rotate_left apply
+    pub const fn rotate_right(self, n: usize) -> Self {
+        self.rotate_left(self.current_size - n % self.current_size)
+    }
+
+}
+
+pub mod const_helpers {
+    #[macro_export]
+    macro_rules! max {
+        ($x:expr, $y:expr) => {
+            if $x > $y { $x } else { $y }
+        };
+    }
+
+    #[macro_export]
+    macro_rules! min {
+        ($x:expr, $y:expr) => {
+            if $x < $y { $x } else { $y }
+        };
+    }
+
+    pub(crate) use max;
+    pub(crate) use min;
+}
+
+
+
+// TESTS
+#[cfg(all(test, feature = "std"))]
+// lints newer than the original tests
+#[allow(clippy::unnecessary_cast, clippy::format_in_format_args)]
+mod tests {
+    use crate::bigint::BigInt;
+    type BigIntTest = BigInt<100>;
+
+    /// A pseudo-random decimal string with the given number of digits and no leading zero
+    fn random_string(seed: &mut u64, digits: usize) -> String {
+        (0..digits).map(|i| {
+            *seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let digit = ((*seed >> 33) % 10) as u8;
+            char::from(b'0' + if i == 0 { digit % 9 + 1 } else { digit })
+        }).collect()
+    }
+
+    /// Pseudo-random values with every length from 1 to max_len digits, odd lengths negative
+    fn random_values(seed: u64, max_len: usize) -> impl Iterator<Item = BigIntTest> {
+        let mut seed = seed;
+        (1..=max_len).map(move |len| {
+            let x: BigIntTest = BigInt::from_str(&random_string(&mut seed, len));
+            if len % 2 == 1 { -x } else { x }
+        })
+    }
+
+    #[test]
+    fn from_positive() {
+        const STR: &str = "123456789123456789123456789123456789";
+        const X: BigIntTest = BigInt::from_str(STR);
+        const DIGITS: [u8; X.max_size()] = { 
+            let mut result =  [0u8; X.max_size()]; 
+
+            let digits = BigIntTest::DEFAULT.max_size() - STR.len();
+            let mut i = digits;
+            while i < BigIntTest::DEFAULT.max_size() {
+                result[i as usize] = ((i - digits) % 9 + 1) as u8;
+                i+=1;
+            }
+
+            result
+        };
+
+        const EXPECTED: BigIntTest = BigInt { is_negative: false, digits:DIGITS, current_size: STR.len()}; 
+        assert_eq!(X, EXPECTED);
+    }
+
+    #[test]
+    fn from_negative() {
+        const STR: &str = "-123456789123456789123456789123456789";
+        const X: BigIntTest = BigInt::from_str(STR);
+        const DIGITS: [u8; X.max_size()] = { 
+            let mut result =  [0u8; X.max_size()]; 
+
+            let digits = BigIntTest::DEFAULT.max_size() - STR.len() + 1;
+            let mut i = digits;
+            while i < BigIntTest::DEFAULT.max_size() {
+                result[i as usize] = ((i - digits) % 9 + 1) as u8;
+                i+=1;
+            }
+
+            result
+        };
+
+        const EXPECTED: BigIntTest = BigInt { is_negative: true, digits:DIGITS, current_size: STR.len() - 1};
+        assert_eq!(X, EXPECTED);
+    }
+
+    #[test]
+    fn from_i128() {
+        {
+            const NUM: i128 = 1234567891234567891;
+            const X: BigIntTest = BigInt::from_i128(NUM);
+            const Y: BigIntTest = BigInt::from_str("1234567891234567891");
+            assert_eq!(X, Y);
+        }
+
+        {
+            const NUM: i128 = -1234567891234567891;
+            const X: BigIntTest = BigInt::from_i128(NUM);
+            const Y: BigIntTest = BigInt::from_str("-1234567891234567891");
+            assert_eq!(X, Y);
+        }
+    }
+
+    #[test]
+    fn less() {
+        for i in -1000..1000 {
+            for j in -1000..1000 {
+                let i_str = i.to_string();
+                let j_str = j.to_string();
+                let i1: BigIntTest = BigInt::from_str(&i_str);
+                let j1: BigIntTest = BigInt::from_str(&j_str);
+                let result = i < j;
+                let expected = i1 < j1; 
+                assert_eq!(result, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn greater() {
+        for i in -1000..1000 {
+            for j in -1000..1000 {
+                let i_str = i.to_string();
+                let j_str = j.to_string();
+                let i1: BigIntTest = BigInt::from_str(&i_str);
+                let j1: BigIntTest = BigInt::from_str(&j_str);
+                let result = i > j;
+                let expected = i1 > j1;
+                assert_eq!(result, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn equal() {
+        for i in -1000..1000 {
+            for j in -1000..1000 {
+                let i_str = i.to_string();
+                let j_str = j.to_string();
+                let i1: BigIntTest = BigInt::from_str(&i_str);
+                let j1: BigIntTest = BigInt::from_str(&j_str);
+                let result = i == j;
+                let expected = i1 == j1;
+                assert_eq!(result, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn add(){
+        {
+            const STR1: &str = "-1";
+            const STR2: &str = "999";
+            const RESULT: &str = "998";
+            const X: BigIntTest = BigInt::from_str(STR1);
+            const Y: BigIntTest = BigInt::from_str(STR2);
+            
+            const Z: BigIntTest = X.add(Y);
+
+            const EXPECTED: BigIntTest = BigInt::from_str(RESULT);
+            assert_eq!(Z, EXPECTED);
+        }
+        {
+            const STR1: &str = "-123456789123456789123456789123456789";
+            const STR2: &str = "-987654321987654321987654321987654321";
+            const RESULT: &str = "-1111111111111111111111111111111111110";
+            const X: BigIntTest = BigInt::from_str(STR1);
+            const Y: BigIntTest = BigInt::from_str(STR2);
+
+            const Z: BigIntTest = X.add(Y);
+
+            const EXPECTED: BigIntTest = BigInt::from_str(RESULT);
+            assert_eq!(Z, EXPECTED);
+        }
+    }
+
+    #[test]
+    fn add_2() {
+        // positive + positive
+        for x in -1000..=1000 {
+            for y in -1000..=1000 {
+
+                let x_str = x.to_string();
+                let y_str = y.to_string();
+                let result = (x + y).to_string();
+                let x1: BigIntTest = BigInt::from_str(&x_str);
+                let y1: BigIntTest = BigInt::from_str(&y_str);
+                let result1: BigIntTest = x1 + y1;
+                let expected: BigIntTest = BigInt::from_str(&result);
+                assert_eq!(result1, expected, "{}", format!("{} + {} = {}", x, y, result));
+            }
+        }
+    }
+
+    #[test]
+    fn sub(){
+        {
+            const STR2: &str = "987654321987654321987654321987654321";
+            const STR1: &str = "123456789123456789123456789123456789";
+
+            const RESULT: &str = "864197532864197532864197532864197532";
+
+            const X: BigIntTest = BigInt::from_str(STR1);
+            const Y: BigIntTest = BigInt::from_str(STR2);
+            const Z: BigIntTest = Y.sub(X);
+
+            const EXPECTED: BigIntTest = BigInt::from_str(RESULT);
+            assert_eq!(Z, EXPECTED);
+        }
+        {
+            const STR2: &str = "-987654321987654321987654321987654321";
+            const STR1: &str = "-123456789123456789123456789123456789";
+
+            const RESULT: &str = "-864197532864197532864197532864197532";
+
+            const X: BigIntTest = BigInt::from_str(STR1);
+            const Y: BigIntTest = BigInt::from_str(STR2);
+            const Z: BigIntTest = Y.sub(X);
+
+            const EXPECTED: BigIntTest = BigInt::from_str(RESULT);
+            assert_eq!(Z, EXPECTED);
+        }
+
+
+    }
+
+    #[test]
+    fn sub_2() {
+        for x in -1000..=1000i32 {
+            for y in -1000..=1000i32 {
+                let x_str = x.to_string();
+                let y_str = y.to_string();
+                let result = (x - y).to_string();
+                let x1: BigIntTest = BigInt::from_str(&x_str);
+                let y1: BigIntTest = BigInt::from_str(&y_str);
+                let result1: BigIntTest = x1 - y1;
+                let expected: BigIntTest = BigInt::from_str(&result);
+                assert_eq!(result1, expected, "{}", format!("{} - {} = {}", x, y, result));
+            }
+        }
+    }
+
+    #[test]
+    fn mul(){
+        for x in -1000..=1000i32 {
+            for y in -1000..=1000i32 {
+                let x_str = x.to_string();
+                let y_str = y.to_string();
+                let result = (x * y).to_string();
+                let x1: BigIntTest = BigInt::from_str(&x_str);
+                let y1: BigIntTest = BigInt::from_str(&y_str);
+                let result1: BigIntTest = x1 * y1;
+                let expected: BigIntTest = BigInt::from_str(&result);
+                assert_eq!(result1, expected, "{}", format!("{} * {} = {}", x, y, result));
+            }
+        }
+    }
+
+    #[test]
+    fn div(){
+        type I3 = BigInt<3>;
+        for x in -999..=999i32 {
+            for y in -999..=999i32 {
+                if y == 0 {
+                    continue;
+                }
+                let x_str = x.to_string();
+                let y_str = y.to_string();
+                let result = (x / y).to_string();
+                let result_rem = (x % y).to_string();
+                let x1: I3 = BigInt::from_str(&x_str);
+                let y1: I3 = BigInt::from_str(&y_str);
+                let (result1, result2) = x1.div(y1);
+                let expected: I3 = BigInt::from_str(&result);
+                let expected_rem: I3 = BigInt::from_str(&result_rem);
+                assert_eq!(result1, expected, "{}", format!("{} / {} = {}", x, y, result));
+                assert_eq!(result2, expected_rem, "{}", format!("{} % {} = {}", x, y, result_rem));
+            }
+        }
+    }
+
+    #[test]
+    fn div_2() {
+        const A: BigIntTest = BigInt::from_str("33322211112345678987654321");
+        const B: BigIntTest = BigInt::from_str("15485863");
+        const DIV: BigIntTest = BigInt::from_str("2151782636353277759");
+        const REM: BigIntTest = BigInt::from_str("10833304");
+
+        const RES: (BigIntTest, BigIntTest) = A.div(B);
+        assert_eq!(RES.0, DIV);
+        assert_eq!(RES.1, REM);
+    }
+
+    #[test]
+    fn pow() {
+        const X: BigIntTest = BigInt::from_i128(3).pow(40);
+        const EXPECTED: BigIntTest = BigInt::from_str("12157665459056928801");
+        assert_eq!(X, EXPECTED);
+
+        for x in [-7i128, -2, -1, 0, 1, 2, 13, 1000] {
+            let one: BigIntTest = BigInt::from_i128(1);
+            assert_eq!(BigIntTest::from_i128(x).pow(0), one, "{}^0 = 1", x);
+        }
+
+        for x in -20..=20i128 {
+            for exp in 1..=20u32 {
+                let result = x.pow(exp);
+                let x1: BigIntTest = BigInt::from_i128(x);
+                let expected: BigIntTest = BigInt::from_i128(result);
+                assert_eq!(x1.pow(exp), expected, "{}^{} = {}", x, exp, result);
+            }
+        }
+    }
+
+    #[test]
+    fn pow_mod() {
+        const A: BigIntTest = BigInt::from_i128(2).pow_mod(BigInt::from_i128(1000), BigInt::from_i128(1_000_000_007));
+        assert_eq!(A, BigInt::from_i128(688423210));
+
+        const B: BigIntTest = BigInt::from_i128(123456789).pow_mod(BigInt::from_i128(987654321), BigInt::from_i128(998244353));
+        assert_eq!(B, BigInt::from_i128(730701112));
+
+        for m in [1i128, 2, 3, 7, 10, 13, 97, 1000, 12345] {
+            for x in -12..=12i128 {
+                let mut expected = 1 % m;
+                for exp in 0..=40i128 {
+                    let x1: BigIntTest = BigInt::from_i128(x);
+                    let result = x1.pow_mod(BigInt::from_i128(exp), BigInt::from_i128(m));
+                    assert_eq!(result, BigInt::from_i128(expected), "{}^{} mod {} = {}", x, exp, m, expected);
+                    expected = (expected * x).rem_euclid(m);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn gcd() {
+        fn gcd_i128(a: i128, b: i128) -> i128 {
+            if b == 0 { a.abs() } else { gcd_i128(b, a % b) }
+        }
+
+        const G: BigIntTest = BigInt::from_str("-123456789123456789").gcd(BigInt::from_str("987654321"));
+        assert_eq!(G, BigInt::from_i128(gcd_i128(-123456789123456789, 987654321)));
+
+        for x in -60..=60i128 {
+            for y in -60..=60i128 {
+                let x1: BigIntTest = BigInt::from_i128(x);
+                let y1: BigIntTest = BigInt::from_i128(y);
+                let expected = gcd_i128(x, y);
+                assert_eq!(x1.gcd(y1), BigInt::from_i128(expected), "gcd({}, {}) = {}", x, y, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn lcm() {
+        fn gcd_i128(a: i128, b: i128) -> i128 {
+            if b == 0 { a.abs() } else { gcd_i128(b, a % b) }
+        }
+
+        const LCM: BigIntTest = BigInt::from_i128(-4).lcm(BigInt::from_i128(6));
+        assert_eq!(LCM, BigInt::from_i128(12));
+
+        for x in -60..=60i128 {
+            for y in -60..=60i128 {
+                let x1: BigIntTest = BigInt::from_i128(x);
+                let y1: BigIntTest = BigInt::from_i128(y);
+                let expected = if x == 0 || y == 0 { 0 } else { (x / gcd_i128(x, y) * y).abs() };
+                assert_eq!(x1.lcm(y1), BigInt::from_i128(expected), "lcm({}, {}) = {}", x, y, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn isqrt() {
+        const R: BigIntTest = BigInt::from_str("152415787532388367501905199875019052100").isqrt();
+        assert_eq!(R, BigInt::from_str("12345678901234567890"));
+
+        for k in 0..2000i128 {
+            let n: BigIntTest = BigInt::from_i128(k * k);
+            assert_eq!(n.isqrt(), BigInt::from_i128(k), "isqrt({})", k * k);
+        }
+
+        let one: BigIntTest = BigInt::from_i128(1);
+        let mut seed = 12345i128;
+        for _ in 0..500 {
+            seed = (seed * 1103515245 + 12345) % (1 << 62);
+            let n: BigIntTest = BigInt::from_i128(seed).mul(BigInt::from_i128(seed / 7 + 1));
+            let r = n.isqrt();
+            assert!(!n.less(r.mul(r)), "isqrt({}) = {}", n, r);
+            assert!(n.less((r + one).mul(r + one)), "isqrt({}) = {}", n, r);
+        }
+    }
+
+    #[test]
+    fn isqrt_small_max_size() {
+        for x in 0..10i128 {
+            let r = (0..=x).filter(|r| r * r <= x).max().unwrap();
+            assert_eq!(BigInt::<1>::from_i128(x).isqrt(), BigInt::from_i128(r), "isqrt({})", x);
+        }
+        for x in 0..100i128 {
+            let r = (0..=x).filter(|r| r * r <= x).max().unwrap();
+            assert_eq!(BigInt::<2>::from_i128(x).isqrt(), BigInt::from_i128(r), "isqrt({})", x);
+        }
+        assert_eq!(BigInt::<5>::from_i128(99999).isqrt(), BigInt::from_i128(316));
+    }
+
+    #[test]
+    #[should_panic]
+    fn isqrt_negative() {
+        BigIntTest::from_i128(-4).isqrt();
+    }
+
+    #[test]
+    fn neg() {
+        const X: BigIntTest = BigInt::from_str("-123456789123456789").neg();
+        assert_eq!(X, BigInt::from_str("123456789123456789"));
+
+        let zero: BigIntTest = BigInt::from_i128(0);
+        for x in -1000..=1000i128 {
+            let x1: BigIntTest = BigInt::from_i128(x);
+            assert_eq!(-x1, BigInt::from_i128(-x));
+            assert_eq!(-x1 + x1, zero, "-{} + {} = 0", x, x);
+        }
+
+        let neg_zero = -zero;
+        assert_eq!(neg_zero, zero);
+        assert!(!neg_zero.is_negative);
+    }
+
+    #[test]
+    fn abs() {
+        const D: BigIntTest = BigInt::from_i128(12).sub(BigInt::from_i128(1000)).abs();
+        assert_eq!(D, BigInt::from_i128(988));
+
+        let x: BigIntTest = BigInt::from_str("-123456789123456789");
+        assert_eq!(x.abs(), BigInt::from_str("123456789123456789"));
+        assert_eq!(x.abs().size(), x.size());
+
+        let zero: BigIntTest = BigInt::from_i128(0);
+        assert_eq!(zero.abs(), zero);
+    }
+
+    #[test]
+    fn sort() {
+        let mut values: Vec<i128> = vec![5, -3, 0, 1000, -1000, 42, -42, 7, -1, 1, 999999999999, -999999999999, 0];
+        let mut big: Vec<BigIntTest> = values.iter().map(|&x| BigInt::from_i128(x)).collect();
+        values.sort();
+        big.sort();
+        let expected: Vec<BigIntTest> = values.iter().map(|&x| BigInt::from_i128(x)).collect();
+        assert_eq!(big, expected);
+
+        let map: std::collections::BTreeMap<BigIntTest, i128> = values.iter().map(|&x| (BigInt::from_i128(x), x)).collect();
+        assert_eq!(map.len(), values.len() - 1);
+        assert_eq!(map.keys().next(), Some(&BigInt::from_i128(-999999999999)));
+    }
+
+    #[test]
+    fn hash() {
+        fn hash_of(x: &BigIntTest) -> u64 {
+            use std::hash::{Hash, Hasher};
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            x.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let mut set = std::collections::HashSet::new();
+        for x in -100..=100i128 {
+            set.insert(BigIntTest::from_i128(x));
+            set.insert(BigIntTest::from_str(&x.to_string()));
+            set.insert(BigIntTest::from_i128(x).add(BigInt::from_i128(1000)).sub(BigInt::from_i128(1000)));
+        }
+        assert_eq!(set.len(), 201);
+
+        let zero: BigIntTest = BigInt::from_i128(0);
+        let mut negative_zero = zero;
+        negative_zero.is_negative = true;
+        assert_eq!(hash_of(&zero), hash_of(&BigInt::from_str("0")));
+        assert_eq!(hash_of(&zero), hash_of(&BigInt::from_i128(5).sub(BigInt::from_i128(5))));
+        assert_eq!(hash_of(&zero), hash_of(&negative_zero));
+    }
+
+    #[test]
+    fn try_from_str() {
+        use crate::bigint::ParseBigIntError;
+
+        for x in -1000..=1000i128 {
+            assert_eq!(BigIntTest::try_from_str(&x.to_string()), Ok(BigInt::from_i128(x)));
+        }
+        assert_eq!(BigIntTest::try_from_str("-000123"), Ok(BigInt::from_i128(-123)));
+        assert_eq!(BigIntTest::try_from_str("-0"), Ok(BigInt::from_i128(0)));
+        assert_eq!(BigIntTest::try_from_str("000"), Ok(BigInt::from_i128(0)));
+
+        assert_eq!(BigIntTest::try_from_str(""), Err(ParseBigIntError::Empty));
+        assert_eq!(BigIntTest::try_from_str("-"), Err(ParseBigIntError::Empty));
+        assert_eq!(BigIntTest::try_from_str("12x45"), Err(ParseBigIntError::InvalidDigit('x')));
+        assert_eq!(BigIntTest::try_from_str("--1"), Err(ParseBigIntError::InvalidDigit('-')));
+        assert_eq!(BigIntTest::try_from_str("1é"), Err(ParseBigIntError::InvalidDigit('é')));
+
+        type I5 = BigInt<5>;
+        assert_eq!(I5::try_from_str("99999"), Ok(BigInt::from_i128(99999)));
+        assert_eq!(I5::try_from_str("-0099999"), Ok(BigInt::from_i128(-99999)));
+        assert_eq!(I5::try_from_str("100000"), Err(ParseBigIntError::Overflow));
+    }
+
+    #[test]
+    fn parse() {
+        use crate::bigint::ParseBigIntError;
+
+        for x in -1000..=1000i128 {
+            let x1: BigIntTest = x.to_string().parse().unwrap();
+            assert_eq!(x1, BigInt::from_i128(x));
+            assert_eq!(x1.to_string().parse::<BigIntTest>(), Ok(x1));
+        }
+        let big = "-123456789123456789123456789123456789";
+        assert_eq!(big.parse::<BigIntTest>().unwrap().to_string(), big);
+        assert_eq!("+123".parse::<BigIntTest>(), Ok(BigInt::from_i128(123)));
+
+        assert_eq!("".parse::<BigIntTest>(), Err(ParseBigIntError::Empty));
+        assert_eq!("+".parse::<BigIntTest>(), Err(ParseBigIntError::Empty));
+        assert_eq!("+-1".parse::<BigIntTest>(), Err(ParseBigIntError::InvalidDigit('-')));
+        assert_eq!(" 1".parse::<BigIntTest>(), Err(ParseBigIntError::InvalidDigit(' ')));
+        assert_eq!("1.5".parse::<BigIntTest>(), Err(ParseBigIntError::InvalidDigit('.')));
+    }
+
+    #[test]
+    fn from_str_radix() {
+        use crate::bigint::ParseBigIntError;
+
+        fn to_radix(x: i128, radix: u32) -> String {
+            let mut digits = Vec::new();
+            let mut value = x.unsigned_abs();
+            loop {
+                digits.push(std::char::from_digit((value % radix as u128) as u32, radix).unwrap());
+                value /= radix as u128;
+                if value == 0 {
+                    break;
+                }
+            }
+            if x < 0 {
+                digits.push('-');
+            }
+            digits.iter().rev().collect()
+        }
+
+        assert_eq!(BigIntTest::from_str_radix("ff", 16), Ok(BigInt::from_i128(255)));
+        assert_eq!(BigIntTest::from_str_radix("-FF", 16), Ok(BigInt::from_i128(-255)));
+        assert_eq!(BigIntTest::from_str_radix("+1010", 2), Ok(BigInt::from_i128(10)));
+        assert_eq!(BigIntTest::from_str_radix("zZ", 36), Ok(BigInt::from_i128(36 * 35 + 35)));
+        assert_eq!(
+            BigIntTest::from_str_radix("ffffffffffffffffffffffffffffffffffffffff", 16),
+            Ok(BigInt::from_str("1461501637330902918203684832716283019655932542975"))
+        );
+
+        let mut seed = 987654321i128;
+        for _ in 0..300 {
+            seed = (seed * 1103515245 + 12345) % (1 << 62) - (1 << 61);
+            for radix in [2, 3, 8, 10, 16, 36] {
+                let s = to_radix(seed, radix);
+                assert_eq!(BigIntTest::from_str_radix(&s, radix), Ok(BigInt::from_i128(seed)), "{} in radix {}", s, radix);
+            }
+        }
+
+        assert_eq!(BigIntTest::from_str_radix("", 16), Err(ParseBigIntError::Empty));
+        assert_eq!(BigIntTest::from_str_radix("102", 2), Err(ParseBigIntError::InvalidDigit('2')));
+        assert_eq!(BigIntTest::from_str_radix("fg", 16), Err(ParseBigIntError::InvalidDigit('g')));
+
+        type I3 = BigInt<3>;
+        assert_eq!(I3::from_str_radix("3e7", 16), Ok(BigInt::from_i128(999)));
+        assert_eq!(I3::from_str_radix("-1111100111", 2), Ok(BigInt::from_i128(-999)));
+        assert_eq!(I3::from_str_radix("3e8", 16), Err(ParseBigIntError::Overflow));
+        assert_eq!(I3::from_str_radix("1111101000", 2), Err(ParseBigIntError::Overflow));
+
+        type I1 = BigInt<1>;
+        assert_eq!(I1::from_str_radix("5", 36), Ok(BigInt::from_i128(5)));
+        assert_eq!(I1::from_str_radix("-9", 10), Ok(BigInt::from_i128(-9)));
+        assert_eq!(I1::from_str_radix("1001", 2), Ok(BigInt::from_i128(9)));
+        assert_eq!(I1::from_str_radix("0000", 16), Ok(BigInt::from_i128(0)));
+        assert_eq!(I1::from_str_radix("a", 16), Err(ParseBigIntError::Overflow));
+        assert_eq!(I1::from_str_radix("1010", 2), Err(ParseBigIntError::Overflow));
+        assert_eq!(I1::from_str_radix("10", 36), Err(ParseBigIntError::Overflow));
+    }
+
+    #[test]
+    fn to_str_radix() {
+        assert_eq!(BigIntTest::from_i128(255).to_str_radix(16), "ff");
+        assert_eq!(BigIntTest::from_i128(-255).to_str_radix(16), "-ff");
+        assert_eq!(BigIntTest::from_i128(0).to_str_radix(2), "0");
+        assert_eq!(BigIntTest::from_i128(10).to_str_radix(2), "1010");
+        assert_eq!(BigIntTest::from_i128(35).to_str_radix(36), "z");
+        assert_eq!(BigIntTest::from_i128(-123456789).to_str_radix(10), "-123456789");
+
+        let mut seed = 123456789i128;
+        for _ in 0..200 {
+            seed = (seed * 1103515245 + 12345) % (1 << 62) - (1 << 61);
+            let x: BigIntTest = BigInt::from_i128(seed);
+            assert_eq!(x.to_str_radix(16), if seed < 0 { format!("-{:x}", -seed) } else { format!("{:x}", seed) });
+            for radix in [2, 7, 10, 16, 36] {
+                assert_eq!(BigIntTest::from_str_radix(&x.to_str_radix(radix), radix), Ok(x));
+            }
+        }
+    }
+
+    #[test]
+    fn to_str_radix_small_max_size() {
+        assert_eq!(BigInt::<1>::from_i128(5).to_str_radix(2), "101");
+        assert_eq!(BigInt::<1>::from_i128(-9).to_str_radix(16), "-9");
+        assert_eq!(BigInt::<1>::from_i128(9).to_str_radix(36), "9");
+        assert_eq!(BigInt::<2>::from_i128(99).to_str_radix(36), "2r");
+    }
+
+    #[test]
+    fn hex() {
+        let x: BigIntTest = BigInt::from_i128(0xdead);
+        assert_eq!(format!("{:x}", x), "dead");
+        assert_eq!(format!("{:X}", x), "DEAD");
+        assert_eq!(format!("{:#x}", x), "0xdead");
+        assert_eq!(format!("{:#X}", x), "0XDEAD");
+        assert_eq!(format!("{:#x}", -x), "-0xdead");
+        assert_eq!(format!("{:x}", BigIntTest::from_i128(0)), "0");
+        assert_eq!(format!("{:#x}", BigIntTest::from_i128(0)), "0x0");
+    }
+
+    #[test]
+    fn binary() {
+        let x: BigIntTest = BigInt::from_i128(10);
+        assert_eq!(format!("{:b}", x), "1010");
+        assert_eq!(format!("{:#b}", x), "0b1010");
+        assert_eq!(format!("{:b}", -x), "-1010");
+        assert_eq!(format!("{:#b}", -x), "-0b1010");
+        assert_eq!(format!("{:b}", BigIntTest::from_i128(0)), "0");
+        assert_eq!(format!("{:b}", BigIntTest::from_i128(1 << 100)), format!("{:b}", 1i128 << 100));
+    }
+
+    #[test]
+    fn debug() {
+        let x: BigInt<200> = BigInt::from_i128(-12345);
+        assert_eq!(format!("{:?}", x), "BigInt<200>(-12345)");
+        assert_eq!(format!("{:?}", BigIntTest::from_i128(0)), "BigInt<100>(0)");
+    }
+
+    #[test]
+    fn assign_ops() {
+        let original: BigIntTest = BigInt::from_str("-123456789123456789");
+        let y: BigIntTest = BigInt::from_str("987654321987");
+
+        let mut x = original;
+        x += y;
+        assert_eq!(x, original + y);
+        x -= y;
+        assert_eq!(x, original);
+        x *= y;
+        assert_eq!(x, original * y);
+        x /= y;
+        assert_eq!(x, original);
+        x %= y;
+        assert_eq!(x, original % y);
+    }
+
+    #[test]
+    fn sum_product() {
+        let ones = vec![BigIntTest::from_i128(1); 100];
+        assert_eq!(ones.iter().sum::<BigIntTest>(), BigInt::from_i128(100));
+        assert_eq!(ones.into_iter().sum::<BigIntTest>(), BigInt::from_i128(100));
+
+        let values: Vec<BigIntTest> = (1..=10).map(BigInt::from_i128).collect();
+        assert_eq!(values.iter().product::<BigIntTest>(), BigInt::from_i128(3628800));
+        assert_eq!(values.into_iter().product::<BigIntTest>(), BigInt::from_i128(3628800));
+
+        assert_eq!(std::iter::empty::<BigIntTest>().sum::<BigIntTest>(), BigInt::from_i128(0));
+        assert_eq!(std::iter::empty::<BigIntTest>().product::<BigIntTest>(), BigInt::from_i128(1));
+    }
+
+    #[test]
+    fn to_i128() {
+        use crate::bigint::TryFromBigIntError;
+
+        for x in -1000..=1000i128 {
+            assert_eq!(BigIntTest::from_i128(x).to_i128(), Some(x));
+        }
+
+        const MAX: BigIntTest = BigInt::from_str("170141183460469231731687303715884105727");
+        const MIN: BigIntTest = BigInt::from_str("-170141183460469231731687303715884105728");
+        const ABOVE_MAX: BigIntTest = BigInt::from_str("170141183460469231731687303715884105728");
+        const BELOW_MIN: BigIntTest = BigInt::from_str("-170141183460469231731687303715884105729");
+        assert_eq!(MAX.to_i128(), Some(i128::MAX));
+        assert_eq!(MIN.to_i128(), Some(i128::MIN));
+        assert_eq!(ABOVE_MAX.to_i128(), None);
+        assert_eq!(BELOW_MIN.to_i128(), None);
+        assert_eq!(BigIntTest::from_str("1000000000000000000000000000000000000000").to_i128(), None);
+
+        assert_eq!(i128::try_from(MAX), Ok(i128::MAX));
+        assert_eq!(i128::try_from(MIN), Ok(i128::MIN));
+        assert_eq!(i128::try_from(ABOVE_MAX), Err(TryFromBigIntError));
+        assert_eq!(i128::try_from(BELOW_MIN), Err(TryFromBigIntError));
+    }
+
+    #[test]
+    fn from_i128_bounds() {
+        const MIN: BigIntTest = BigInt::from_i128(i128::MIN);
+        const MAX: BigIntTest = BigInt::from_i128(i128::MAX);
+        assert_eq!(MIN, BigInt::from_str("-170141183460469231731687303715884105728"));
+        assert_eq!(MAX, BigInt::from_str("170141183460469231731687303715884105727"));
+        assert_eq!(MIN.to_string(), i128::MIN.to_string());
+        assert_eq!(MAX.to_string(), i128::MAX.to_string());
+    }
+
+    #[test]
+    fn from_primitive() {
+        const U128_MAX: BigIntTest = BigInt::from_u128(u128::MAX);
+        assert_eq!(U128_MAX.to_string(), "340282366920938463463374607431768211455");
+
+        assert_eq!(BigIntTest::from(u8::MAX).to_string(), u8::MAX.to_string());
+        assert_eq!(BigIntTest::from(u16::MAX).to_string(), u16::MAX.to_string());
+        assert_eq!(BigIntTest::from(u32::MAX).to_string(), u32::MAX.to_string());
+        assert_eq!(BigIntTest::from(u64::MAX).to_string(), u64::MAX.to_string());
+        assert_eq!(BigIntTest::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(BigIntTest::from(i8::MAX).to_string(), i8::MAX.to_string());
+        assert_eq!(BigIntTest::from(i16::MAX).to_string(), i16::MAX.to_string());
+        assert_eq!(BigIntTest::from(i32::MAX).to_string(), i32::MAX.to_string());
+        assert_eq!(BigIntTest::from(i64::MAX).to_string(), i64::MAX.to_string());
+        assert_eq!(BigIntTest::from(i128::MAX).to_string(), i128::MAX.to_string());
+
+        assert_eq!(BigIntTest::from(i8::MIN).to_string(), i8::MIN.to_string());
+        assert_eq!(BigIntTest::from(i64::MIN).to_string(), i64::MIN.to_string());
+        assert_eq!(BigIntTest::from(0u8), BigInt::from_i128(0));
+    }
+
+    #[test]
+    fn from_primitive_fits_exactly() {
+        assert_eq!(BigInt::<3>::from_i128(-999).to_string(), "-999");
+        assert_eq!(BigInt::<1>::from_u128(0).to_string(), "0");
+        assert_eq!(BigInt::<39>::from_u128(u128::MAX).to_string(), u128::MAX.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "number too large for BigInt<MAX_SIZE>")]
+    fn from_i128_overflow() {
+        BigInt::<3>::from_i128(-1000);
+    }
+
+    #[test]
+    #[should_panic(expected = "number too large for BigInt<MAX_SIZE>")]
+    fn from_u128_overflow() {
+        BigInt::<38>::from_u128(u128::MAX);
+    }
+
+    #[test]
+    fn checked_mul() {
+        type I5 = BigInt<5>;
+        let a: I5 = BigInt::from_i128(123);
+        assert_eq!(a.checked_mul(BigInt::from_i128(456)), Some(BigInt::from_i128(56088)));
+        assert_eq!(a.checked_mul(BigInt::from_i128(999)), None);
+        assert_eq!(a.checked_mul(BigInt::from_i128(4567)), None);
+        assert_eq!(a.checked_mul(BigInt::from_i128(-81)), Some(BigInt::from_i128(-9963)));
+        assert_eq!(I5::from_i128(99999).checked_mul(BigInt::from_i128(1)), Some(BigInt::from_i128(99999)));
+        assert_eq!(I5::from_i128(99999).checked_mul(BigInt::from_i128(2)), None);
+        assert_eq!(I5::from_i128(0).checked_mul(BigInt::from_i128(99999)), Some(BigInt::from_i128(0)));
+
+        type I3 = BigInt<3>;
+        for x in -999..=999i128 {
+            for y in [-999i128, -100, -37, -10, -2, -1, 0, 1, 2, 3, 9, 10, 11, 99, 101, 500, 999] {
+                let x1: I3 = BigInt::from_i128(x);
+                let y1: I3 = BigInt::from_i128(y);
+                let expected = if (x * y).abs() <= 999 { Some(BigInt::from_i128(x * y)) } else { None };
+                assert_eq!(x1.checked_mul(y1), expected, "{} * {}", x, y);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn mul_overflow() {
+        let x: BigInt<5> = BigInt::from_i128(123);
+        let _ = x * x * x;
+    }
+
+    #[test]
+    fn checked_add_sub() {
+        type I3 = BigInt<3>;
+        let x: I3 = BigInt::from_i128(999);
+        let two: I3 = BigInt::from_i128(2);
+        assert_eq!(x.checked_add(two), None);
+        assert_eq!(x.neg().checked_add(two.neg()), None);
+        assert_eq!(x.checked_sub(two.neg()), None);
+        assert_eq!(x.neg().checked_sub(two), None);
+        assert_eq!(x.checked_sub(two), Some(BigInt::from_i128(997)));
+        assert_eq!(x.checked_add(two.neg()), Some(BigInt::from_i128(997)));
+
+        for a in (-999..=999i128).step_by(7) {
+            for b in (-999..=999i128).step_by(11) {
+                let a1: I3 = BigInt::from_i128(a);
+                let b1: I3 = BigInt::from_i128(b);
+                let sum = if (a + b).abs() <= 999 { Some(BigInt::from_i128(a + b)) } else { None };
+                let diff = if (a - b).abs() <= 999 { Some(BigInt::from_i128(a - b)) } else { None };
+                assert_eq!(a1.checked_add(b1), sum, "{} + {}", a, b);
+                assert_eq!(a1.checked_sub(b1), diff, "{} - {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn saturating() {
+        type I3 = BigInt<3>;
+        let max: I3 = BigInt::from_i128(999);
+        let min: I3 = BigInt::from_i128(-999);
+        assert_eq!(I3::max_value(), max);
+        assert_eq!(I3::min_value(), min);
+
+        for a in (-999..=999i128).step_by(13) {
+            for b in (-999..=999i128).step_by(17) {
+                let a1: I3 = BigInt::from_i128(a);
+                let b1: I3 = BigInt::from_i128(b);
+                assert_eq!(a1.saturating_add(b1), BigInt::from_i128((a + b).clamp(-999, 999)), "{} + {}", a, b);
+                assert_eq!(a1.saturating_sub(b1), BigInt::from_i128((a - b).clamp(-999, 999)), "{} - {}", a, b);
+                assert_eq!(a1.saturating_mul(b1), BigInt::from_i128((a * b).clamp(-999, 999)), "{} * {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn mul_1000_digits() {
+        type I2000 = BigInt<2000>;
+        let nines = "9".repeat(1000);
+        let a: I2000 = BigInt::from_str(&nines);
+        let expected = format!("{}8{}1", "9".repeat(999), "0".repeat(999));
+        assert_eq!((a * a).to_string(), expected);
+
+        let b: I2000 = BigInt::from_str(&"1234567890".repeat(100));
+        let c: I2000 = BigInt::from_str(&"9876543210".repeat(100));
+        let product = b * c;
+        assert_eq!(product.size(), 2000);
+        assert_eq!(product / c, b);
+        assert_eq!(product % b, BigInt::from_i128(0));
+    }
+
+    #[test]
+    fn karatsuba() {
+        type I1000 = BigInt<1000>;
+        let mut seed = 42u64;
+
+        for i in 0..30 {
+            let a: I1000 = BigInt::from_str(&random_string(&mut seed, 65 + i * 13));
+            let b: I1000 = BigInt::from_str(&random_string(&mut seed, 65 + (i * 37) % 300));
+            let schoolbook = a.mul_schoolbook(b).0;
+            assert_eq!(a.karatsuba(b), schoolbook);
+            assert_eq!(a * b.neg(), schoolbook.neg());
+        }
+
+        let a: I1000 = BigInt::from_str(&random_string(&mut seed, 500));
+        let b: I1000 = BigInt::from_str(&random_string(&mut seed, 499));
+        let product = a * b;
+        assert_eq!(product, a.mul_schoolbook(b).0);
+        assert_eq!(product / a, b);
+        assert_eq!(product % b, BigInt::from_i128(0));
+    }
+
+    #[test]
+    fn small_values_in_large_buffer() {
+        type I10 = BigInt<10>;
+        type I10000 = BigInt<10000>;
+        for x in (-999..=999i128).step_by(37) {
+            for y in (-999..=999i128).step_by(41) {
+                let (x1, y1) = (I10::from_i128(x), I10::from_i128(y));
+                let (x2, y2) = (I10000::from_i128(x), I10000::from_i128(y));
+                assert_eq!((x1 + y1).to_string(), (x2 + y2).to_string());
+                assert_eq!((x1 - y1).to_string(), (x2 - y2).to_string());
+                assert_eq!((x1 * y1).to_string(), (x2 * y2).to_string());
+                assert_eq!(x1 < y1, x2 < y2);
+                if y != 0 {
+                    assert_eq!((x1 / y1).to_string(), (x2 / y2).to_string());
+                    assert_eq!((x1 % y1).to_string(), (x2 % y2).to_string());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn div_3() {
+        let mut seed = 1234567u64;
+        for _ in 0..20000 {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let x = (seed >> 1) as i128 * if seed & 1 == 0 { 1 } else { -1 };
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let y = (seed >> (1 + seed % 60)) as i128 * if seed & 1 == 0 { 1 } else { -1 };
+            if y == 0 {
+                continue;
+            }
+            let (q, r) = BigIntTest::from_i128(x).div(BigInt::from_i128(y));
+            assert_eq!(q, BigInt::from_i128(x / y), "{} / {}", x, y);
+            assert_eq!(r, BigInt::from_i128(x % y), "{} % {}", x, y);
+        }
+
+        type I200 = BigInt<200>;
+        for i in 0..200 {
+            let a: I200 = BigInt::from_str(&random_string(&mut seed, 1 + (i * 7) % 200));
+            let b: I200 = BigInt::from_str(&random_string(&mut seed, 1 + (i * 13) % 120));
+            let (q, r) = a.div(b);
+            assert_eq!(q * b + r, a, "{} / {}", a, b);
+            assert!(r.less(b), "{} % {}", a, b);
+        }
+    }
+
+    #[test]
+    fn div_signs() {
+        for (x, y) in [(7i128, 3i128), (-7, 3), (7, -3), (-7, -3), (6, 3), (-6, 3), (6, -3), (-6, -3), (2, 5), (-2, 5), (2, -5), (-2, -5)] {
+            let x1: BigIntTest = BigInt::from_i128(x);
+            let y1: BigIntTest = BigInt::from_i128(y);
+            let (q, r) = x1.div(y1);
+            assert_eq!(q, BigInt::from_i128(x / y), "{} / {}", x, y);
+            assert_eq!(r, BigInt::from_i128(x % y), "{} % {}", x, y);
+            assert_eq!(q * y1 + r, x1, "{} == ({} / {}) * {} + {} % {}", x, x, y, y, x, y);
+            assert!(!r.is_negative || x < 0);
+        }
+
+        let a: BigIntTest = BigInt::from_str("-33322211112345678987654321");
+        let b: BigIntTest = BigInt::from_str("15485863");
+        for (x, y) in [(a, b), (a, -b), (-a, b), (-a, -b)] {
+            let (q, r) = x.div(y);
+            assert_eq!(q * y + r, x);
+            assert_eq!(r.is_negative, x.is_negative);
+        }
+    }
+
+    #[test]
+    fn div_euclid() {
+        for x in -50..=50i128 {
+            for y in -12..=12i128 {
+                if y == 0 {
+                    continue;
+                }
+                let x1: BigIntTest = BigInt::from_i128(x);
+                let y1: BigIntTest = BigInt::from_i128(y);
+                assert_eq!(x1.div_euclid(y1), BigInt::from_i128(x.div_euclid(y)), "{}.div_euclid({})", x, y);
+                assert_eq!(x1.rem_euclid(y1), BigInt::from_i128(x.rem_euclid(y)), "{}.rem_euclid({})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn is_odd() {
+        for x in -100..=100i128 {
+            let x1: BigIntTest = BigInt::from_i128(x);
+            assert_eq!(x1.is_odd(), x % 2 != 0);
+            assert_eq!(x1.is_odd(), !x1.is_even());
+        }
+    }
+
+    #[test]
+    fn signum() {
+        const S: BigIntTest = BigInt::from_str("-123456789123456789").signum();
+        assert_eq!(S, BigInt::from_i128(-1));
+        for x in -100..=100i128 {
+            assert_eq!(BigIntTest::from_i128(x).signum(), BigInt::from_i128(x.signum()));
+        }
+
+        let mut negative_zero: BigIntTest = BigInt::from_i128(0);
+        negative_zero.is_negative = true;
+        assert_eq!(negative_zero.signum(), BigInt::from_i128(0));
+    }
+
+    #[test]
+    fn min_max() {
+        const MIN: BigIntTest = BigInt::from_i128(-5).min(BigInt::from_i128(3));
+        const MAX: BigIntTest = BigInt::from_i128(-5).max(BigInt::from_i128(3));
+        assert_eq!(MIN, BigInt::from_i128(-5));
+        assert_eq!(MAX, BigInt::from_i128(3));
+
+        for x in -30..=30i128 {
+            for y in -30..=30i128 {
+                let x1: BigIntTest = BigInt::from_i128(x);
+                let y1: BigIntTest = BigInt::from_i128(y);
+                assert_eq!(x1.min(y1), BigInt::from_i128(x.min(y)), "min({}, {})", x, y);
+                assert_eq!(x1.max(y1), BigInt::from_i128(x.max(y)), "max({}, {})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn clamp() {
+        const C: BigIntTest = BigInt::from_i128(1000).clamp(BigInt::from_i128(-10), BigInt::from_i128(10));
+        assert_eq!(C, BigInt::from_i128(10));
+
+        let lo: BigIntTest = BigInt::from_i128(-10);
+        let hi: BigIntTest = BigInt::from_i128(25);
+        for x in -100..=100i128 {
+            assert_eq!(BigIntTest::from_i128(x).clamp(lo, hi), BigInt::from_i128(x.clamp(-10, 25)));
+        }
+        assert_eq!(BigIntTest::from_i128(7).clamp(hi, hi), hi);
+    }
+
+    #[test]
+    #[should_panic]
+    fn clamp_invalid_range() {
+        BigIntTest::from_i128(0).clamp(BigInt::from_i128(1), BigInt::from_i128(-1));
+    }
+
+    #[test]
+    fn shift_pow10() {
+        const X: BigIntTest = BigInt::from_i128(-12345).shl_pow10(3);
+        assert_eq!(X, BigInt::from_i128(-12345000));
+
+        let ten: BigIntTest = BigInt::from_i128(10);
+        for x in [-987654321i128, -1000, -9, -1, 0, 1, 7, 10, 12345, 999999999999] {
+            let x1: BigIntTest = BigInt::from_i128(x);
+            for n in 0..15u32 {
+                assert_eq!(x1.shl_pow10(n as usize), x1 * ten.pow(n), "{} << {}", x, n);
+                assert_eq!(x1.shr_pow10(n as usize), x1 / ten.pow(n), "{} >> {}", x, n);
+            }
+        }
+    }
+
+    #[test]
+    fn num_digits() {
+        assert_eq!(BigIntTest::from_i128(0).num_digits(), 1);
+        assert_eq!(BigIntTest::from_i128(0).sum_of_digits(), 0);
+        assert_eq!(BigIntTest::from_i128(7).num_digits(), 1);
+        assert_eq!(BigIntTest::from_i128(-12345).num_digits(), 5);
+        assert_eq!(BigIntTest::from_i128(-12345).sum_of_digits(), 15);
+        assert_eq!(BigIntTest::from_i128(1000).sum_of_digits(), 1);
+
+        const X: BigIntTest = BigInt::from_str("999999999999999999999999999999");
+        assert_eq!(X.num_digits(), 30);
+        assert_eq!(X.sum_of_digits(), 270);
+    }
+
+    #[test]
+    fn ilog() {
+        for x in 1..=5000i128 {
+            let x1: BigIntTest = BigInt::from_i128(x);
+            assert_eq!(x1.ilog10(), (x1.size() - 1) as u32);
+            assert_eq!(x1.ilog10(), x.ilog10());
+            assert_eq!(x1.ilog2(), x.ilog2());
+        }
+        assert_eq!(BigIntTest::from_i128(i128::MAX).ilog2(), 126);
+        assert_eq!(BigIntTest::from_str("1000000000000000000000000000000000000000000").ilog10(), 42);
+    }
+
+    #[test]
+    #[should_panic]
+    fn ilog_zero() {
+        BigIntTest::from_i128(0).ilog2();
+    }
+
+    #[test]
+    #[should_panic]
+    fn ilog_negative() {
+        BigIntTest::from_i128(-10).ilog10();
+    }
+
+    #[test]
+    fn compare_i128() {
+        for x in -50..=50i128 {
+            for y in -50..=50i128 {
+                let x1: BigIntTest = BigInt::from_i128(x);
+                assert_eq!(x1 == y, x == y);
+                assert_eq!(y == x1, x == y);
+                assert_eq!(x1 < y, x < y);
+                assert_eq!(x1 > y, x > y);
+                assert_eq!(y < x1, y < x);
+                assert_eq!(x1 <= y, x <= y);
+            }
+        }
+
+        let huge: BigIntTest = BigInt::from_str("1000000000000000000000000000000000000000000");
+        assert!(huge != i128::MAX);
+        assert!(huge > i128::MAX);
+        assert!(-huge < i128::MIN);
+        assert!(i128::MIN > -huge);
+
+        let small: BigInt<3> = BigInt::from_i128(999);
+        assert!(small == 999);
+        assert!(small < 100000);
+        assert!(small > -100000);
+        assert!(small != 100999);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        for s in ["123456789123456789123456789", "-987654321987654321", "0"] {
+            let x: BigIntTest = BigInt::from_str(s);
+            let json = serde_json::to_string(&x).unwrap();
+            assert_eq!(json, format!("\"{}\"", s));
+            assert_eq!(serde_json::from_str::<BigIntTest>(&json).unwrap(), x);
+        }
+        assert!(serde_json::from_str::<BigIntTest>("\"12x\"").is_err());
+        assert!(serde_json::from_str::<BigInt<3>>("\"1000\"").is_err());
+    }
+
+    #[test]
+    fn bytes_be() {
+        assert_eq!(BigIntTest::from_i128(0).to_bytes_be(), (false, vec![]));
+        assert_eq!(BigIntTest::from_bytes_be(true, &[]), BigInt::from_i128(0));
+        assert_eq!(BigIntTest::from_i128(-258).to_bytes_be(), (true, vec![1, 2]));
+        assert_eq!(BigIntTest::from_bytes_be(false, &[0, 0, 1, 0]), BigInt::from_i128(256));
+
+        let mut seed = 31337i128;
+        for _ in 0..300 {
+            seed = (seed * 1103515245 + 12345) % (1 << 62) - (1 << 61);
+            let x: BigIntTest = BigInt::from_i128(seed);
+            let (sign, bytes) = x.to_bytes_be();
+            let expected = seed.unsigned_abs().to_be_bytes();
+            let skip = expected.iter().take_while(|&&b| b == 0).count();
+            assert_eq!(bytes, &expected[skip..]);
+            assert_eq!(BigIntTest::from_bytes_be(sign, &bytes), x);
+        }
+
+        let big: BigIntTest = BigInt::from_str("-123456789123456789123456789123456789123456789123456789");
+        let (sign, bytes) = big.to_bytes_be();
+        assert_eq!(BigIntTest::from_bytes_be(sign, &bytes), big);
+    }
+
+    #[test]
+    fn bytes_be_small_max_size() {
+        for x in -9..=9 {
+            let x1 = BigInt::<1>::from_i128(x);
+            let (sign, bytes) = x1.to_bytes_be();
+            assert_eq!(BigInt::<1>::from_bytes_be(sign, &bytes), x1);
+        }
+        for x in -99..=99 {
+            let x1 = BigInt::<2>::from_i128(x);
+            let (sign, bytes) = x1.to_bytes_be();
+            assert_eq!(BigInt::<2>::from_bytes_be(sign, &bytes), x1);
+        }
+        assert_eq!(BigInt::<2>::from_i128(5).to_bytes_be(), (false, vec![5]));
+        assert_eq!(BigInt::<2>::from_bytes_be(true, &[0, 99]), BigInt::from_i128(-99));
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_bytes_be_overflow() {
+        BigInt::<2>::from_bytes_be(false, &[100]);
+    }
+
+    #[test]
+    fn display() {
+        // the previous implementation, which built a String first
+        fn display_with_string<const N: usize>(x: &BigInt<N>) -> String {
+            let mut result = String::with_capacity(x.current_size + 1);
+            if x.is_negative {
+                result.push('-');
+            }
+            let mut i = N - x.current_size;
+            while i < N {
+                result.push((x.digits[i] + b'0') as char);
+                i += 1;
+            }
+            result
+        }
+
+        for x in -2000..=2000i128 {
+            let x1: BigIntTest = BigInt::from_i128(x);
+            assert_eq!(x1.to_string(), display_with_string(&x1));
+            assert_eq!(x1.to_string(), x.to_string());
+        }
+
+        let mut seed = 7u64;
+        for digits in [63, 64, 65, 127, 128, 129, 500] {
+            let x: BigInt<500> = BigInt::from_str(&random_string(&mut seed, digits));
+            assert_eq!(x.to_string(), display_with_string(&x));
+            assert_eq!((-x).to_string(), display_with_string(&-x));
+        }
+    }
+
+    #[test]
+    fn display_width() {
+        for x in [-12345i128, -1, 0, 7, 42, 123456789] {
+            let x1: BigIntTest = BigInt::from_i128(x);
+            assert_eq!(format!("{:+}", x1), format!("{:+}", x));
+            assert_eq!(format!("{:08}", x1), format!("{:08}", x));
+            assert_eq!(format!("{:+08}", x1), format!("{:+08}", x));
+            assert_eq!(format!("{:8}", x1), format!("{:8}", x));
+            assert_eq!(format!("{:<8}", x1), format!("{:<8}", x));
+            assert_eq!(format!("{:>8}", x1), format!("{:>8}", x));
+            assert_eq!(format!("{:*^9}", x1), format!("{:*^9}", x));
+            assert_eq!(format!("{:_<+10}", x1), format!("{:_<+10}", x));
+            assert_eq!(format!("{:2}", x1), format!("{:2}", x));
+        }
+    }
+
+    #[test]
+    fn display_grouped() {
+        let cases = [
+            (0i128, "0"),
+            (7, "7"),
+            (123, "123"),
+            (1234, "1,234"),
+            (123456, "123,456"),
+            (1234567, "1,234,567"),
+            (-1234567, "-1,234,567"),
+            (-999, "-999"),
+        ];
+        for (x, expected) in cases {
+            let x1: BigIntTest = BigInt::from_i128(x);
+            assert_eq!(format!("{:#}", x1), expected);
+            assert_eq!(format!("{}", x1), x.to_string());
+        }
+
+        let x1: BigIntTest = BigInt::from_i128(1234567);
+        assert_eq!(format!("{:>#12}", x1), "   1,234,567");
+        assert_eq!(format!("{:+#}", x1), "+1,234,567");
+
+        let long: BigIntTest = BigInt::from_str(&"9".repeat(100));
+        let grouped = format!("{:#}", long);
+        assert_eq!(grouped.len(), 100 + 33);
+        assert_eq!(grouped.replace(',', ""), "9".repeat(100));
+        assert!(!grouped.starts_with(',') && !grouped.ends_with(','));
+    }
+
+    #[test]
+    fn exp() {
+        for x in [0i128, 7, -7, 10, 1200, -123456, 1234567890123, i64::MAX as i128, -(u64::MAX as i128)] {
+            let x1: BigIntTest = BigInt::from_i128(x);
+            assert_eq!(format!("{:e}", x1), format!("{:e}", x));
+            assert_eq!(format!("{:E}", x1), format!("{:E}", x));
+            assert_eq!(format!("{:+e}", x1), format!("{:+e}", x));
+            assert_eq!(format!("{:>16e}", x1), format!("{:>16e}", x));
+            assert_eq!(format!("{:016e}", x1), format!("{:016e}", x));
+        }
+
+        let x1: BigIntTest = BigInt::from_i128(123456);
+        assert_eq!(format!("{:.2e}", x1), "1.23e5");
+        assert_eq!(format!("{:.3e}", x1), "1.235e5");
+        assert_eq!(format!("{:.0e}", x1), "1e5");
+        assert_eq!(format!("{:.8e}", x1), "1.23456000e5");
+
+        let x1: BigIntTest = BigInt::from_i128(-99987);
+        assert_eq!(format!("{:.2e}", x1), "-1.00e5");
+        assert_eq!(format!("{:.1E}", x1), "-1.0E5");
+
+        let big: BigIntTest = BigInt::from_str(&format!("12345{}", "0".repeat(34)));
+        assert_eq!(format!("{:e}", big), "1.2345e38");
+        assert_eq!(format!("{:.3e}", big), "1.234e38");
+        assert_eq!(format!("{:.3e}", big + BigInt::from_i128(1)), "1.235e38");
+    }
+
+    #[test]
+    fn exp_precision_rounding() {
+        // ties round to even like the primitives, anything above a tie rounds up
+        for x in [25i128, 35, -25, 250, 251, 1250, 1350, 12500, 12501, 95, 995, 9950, 999, 1050, 1150, 4500000] {
+            let x1: BigIntTest = BigInt::from_i128(x);
+            for precision in 0..4 {
+                assert_eq!(format!("{:.*e}", precision, x1), format!("{:.*e}", precision, x), "{:.*e}", precision, x);
+            }
+        }
+
+        let mut seed = 2718281828i128;
+        for _ in 0..500 {
+            seed = (seed * 1103515245 + 12345) % (1 << 62) - (1 << 61);
+            // few distinct digits make ties likely
+            let x = seed % 100 * 10i128.pow((seed.unsigned_abs() % 15) as u32);
+            let x1: BigIntTest = BigInt::from_i128(x);
+            for precision in 0..4 {
+                assert_eq!(format!("{:.*e}", precision, x1), format!("{:.*e}", precision, x), "{:.*e}", precision, x);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid digit in from_str")]
+    fn from_str_invalid_digit() {
+        BigIntTest::from_str("12x45");
+    }
+
+    #[test]
+    #[should_panic(expected = "no digits in from_str")]
+    fn from_str_empty() {
+        BigIntTest::from_str("");
+    }
+
+    #[test]
+    #[should_panic(expected = "no digits in from_str")]
+    fn from_str_only_minus() {
+        BigIntTest::from_str("-");
+    }
+
+    #[test]
+    #[should_panic(expected = "no digits in from_str")]
+    fn from_str_only_plus() {
+        BigIntTest::from_str("+");
+    }
+
+    #[test]
+    fn from_str_plus_sign() {
+        const X: BigIntTest = BigInt::from_str("+123");
+        assert_eq!(X, BigInt::from_str("123"));
+        assert_eq!(X, BigInt::from_i128(123));
+        assert_eq!(X.size(), 3);
+        assert_eq!(BigIntTest::try_from_str("+123"), Ok(X));
+        assert_eq!(BigIntTest::from_str("+0"), BigInt::from_i128(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "number too large for BigInt<MAX_SIZE>")]
+    fn from_str_too_long() {
+        BigInt::<5>::from_str("123456");
+    }
+
+    #[test]
+    fn from_str_fits_exactly() {
+        use super::ParseBigIntError;
+
+        assert_eq!(BigInt::<5>::from_str("-99999"), BigInt::from_i128(-99999));
+        assert_eq!(BigInt::<5>::try_from_str("123456"), Err(ParseBigIntError::Overflow));
+        assert_eq!(BigInt::<5>::try_from_str("-123456"), Err(ParseBigIntError::Overflow));
+
+        // leading zeros don't count towards MAX_SIZE
+        const ONE: BigInt<5> = BigInt::from_str("000000001");
+        assert_eq!(ONE, BigInt::from_i128(1));
+        assert_eq!(ONE.size(), 1);
+        assert_eq!(BigInt::<5>::from_str("-0000099999"), BigInt::from_i128(-99999));
+        assert_eq!(BigInt::<1>::from_str("+00000000"), BigInt::from_i128(0));
+        assert_eq!(BigInt::<5>::try_from_str("000000001"), Ok(ONE));
+    }
+
+    #[test]
+    #[should_panic(expected = "number too large for BigInt<MAX_SIZE>")]
+    fn from_str_too_long_after_zeros() {
+        BigInt::<5>::from_str("000123456");
+    }
+
+    #[test]
+    fn negative_zero() {
+        let zero: BigIntTest = BigInt::from_i128(0);
+        for s in ["-0", "-000", "0", "+0"] {
+            let x: BigIntTest = BigInt::from_str(s);
+            assert_eq!(x, zero);
+            assert_eq!(x.to_string(), "0");
+        }
+        assert_eq!(BigIntTest::from_str("-007"), BigInt::from_i128(-7));
+
+        let x: BigIntTest = BigInt::from_i128(-5);
+        let y: BigIntTest = BigInt::from_i128(5);
+        for result in [x + y, x - x, x * zero, zero * x, zero / x, x % y, (x * x) / (zero - x * x * x)] {
+            assert_eq!(result, zero);
+            assert_eq!(result.to_string(), "0");
+        }
+    }
+
+    #[test]
+    fn resize() {
+        const X: BigInt<50> = BigInt::from_str("-123456789012345678901234567890");
+        const Y: BigInt<200> = X.resize();
+        assert_eq!(Y, BigInt::from_str("-123456789012345678901234567890"));
+        let square: BigInt<60> = (Y * Y).resize();
+        assert_eq!(square, BigInt::from_str("15241578753238836750495351562536198787501905199875019052100"));
+
+        assert_eq!(X.resize::<30>().resize::<50>(), X);
+        assert_eq!(X.try_resize::<29>(), None);
+        assert_eq!(BigInt::<50>::from_i128(0).resize::<1>(), BigInt::from_i128(0));
+
+        for x in [-99999i128, -1, 0, 42, 12345] {
+            assert_eq!(BigInt::<5>::from_i128(x).resize::<100>(), BigInt::<100>::from_i128(x));
+            assert_eq!(BigInt::<100>::from_i128(x).try_resize::<5>(), Some(BigInt::from_i128(x)));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn resize_too_small() {
+        BigInt::<10>::from_i128(123456).resize::<5>();
+    }
+
+    #[test]
+    fn probable_prime() {
+        let mut seed = 12345u64;
+        let mut rng = || {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            BigIntTest::from_u128((seed >> 16) as u128)
+        };
+
+        const N: usize = 3000;
+        let mut sieve = [true; N];
+        sieve[0] = false;
+        sieve[1] = false;
+        for i in 2..N {
+            if sieve[i] {
+                for j in (i * i..N).step_by(i) {
+                    sieve[j] = false;
+                }
+            }
+        }
+        for (x, &is_prime) in sieve.iter().enumerate() {
+            let x1: BigIntTest = BigInt::from_i128(x as i128);
+            assert_eq!(x1.is_probable_prime(10, &mut rng), is_prime, "{}", x);
+        }
+
+        // Carmichael numbers fool the Fermat test but not Miller-Rabin
+        for x in [561, 1105, 1729, 41041, 825265] {
+            assert!(!BigIntTest::from_i128(x).is_probable_prime(10, &mut rng), "{}", x);
+        }
+        assert!(!BigIntTest::from_i128(-7).is_probable_prime(10, &mut rng));
+
+        // 2^89 - 1 is prime, 2^67 - 1 is not
+        let m89 = BigIntTest::from_i128(2).pow(89) - BigInt::from_i128(1);
+        let m67 = BigIntTest::from_i128(2).pow(67) - BigInt::from_i128(1);
+        assert!(m89.is_probable_prime(10, &mut rng));
+        assert!(!m67.is_probable_prime(10, &mut rng));
+    }
+
+    #[test]
+    fn prime_trial_small_max_size() {
+        let is_prime = |x: i128| x >= 2 && (2..x).all(|d| x % d != 0);
+        for x in -9..=9 {
+            assert_eq!(BigInt::<1>::from_i128(x).is_prime_trial(), is_prime(x), "{}", x);
+        }
+        for x in -99..=99 {
+            assert_eq!(BigInt::<2>::from_i128(x).is_prime_trial(), is_prime(x), "{}", x);
+        }
+    }
+
+    #[test]
+    fn prime_trial() {
+        const { assert!(BigInt::<10>::from_str("1000003").is_prime_trial()) };
+
+        const N: usize = 1000;
+        let mut sieve = [true; N];
+        sieve[0] = false;
+        sieve[1] = false;
+        for i in 2..N {
+            if sieve[i] {
+                for j in (i * i..N).step_by(i) {
+                    sieve[j] = false;
+                }
+            }
+        }
+        for (x, &is_prime) in sieve.iter().enumerate() {
+            assert_eq!(BigIntTest::from_i128(x as i128).is_prime_trial(), is_prime, "{}", x);
+        }
+        for x in [-1, -2, -7, -997] {
+            assert!(!BigIntTest::from_i128(x).is_prime_trial());
+        }
+    }
+
+    #[test]
+    fn choose() {
+        const C: BigIntTest = BigInt::choose(10, 3);
+        assert_eq!(C, BigInt::from_i128(120));
+        assert_eq!(BigIntTest::choose(3, 10), BigInt::from_i128(0));
+        assert_eq!(BigIntTest::choose(0, 0), BigInt::from_i128(1));
+
+        let mut row = vec![1i128];
+        for n in 0..70u32 {
+            for k in 0..=n {
+                let c = BigIntTest::choose(n, k);
+                assert_eq!(c, BigInt::from_i128(row[k as usize]), "C({}, {})", n, k);
+                assert_eq!(c, BigIntTest::choose(n, n - k));
+            }
+            let mut next = vec![1i128; row.len() + 1];
+            for k in 1..row.len() {
+                next[k] = row[k - 1] + row[k];
+            }
+            row = next;
+        }
+
+        assert_eq!(BigIntTest::choose(200, 100), BigInt::from_str("90548514656103281165404177077484163874504589675413336841320"));
+    }
+
+    #[test]
+    fn choose_fits_exactly() {
+        // every C(n, k) that fits into N digits, intermediate values must not overflow
+        fn check<const N: usize>() {
+            let mut row = vec![1i128];
+            for n in 0..=40u32 {
+                for k in 0..=n {
+                    if row[k as usize] < 10i128.pow(N as u32) {
+                        assert_eq!(BigInt::<N>::choose(n, k), BigInt::from_i128(row[k as usize]), "C({}, {})", n, k);
+                    }
+                }
+                let mut next = vec![1i128; row.len() + 1];
+                for k in 1..row.len() {
+                    next[k] = row[k - 1] + row[k];
+                }
+                row = next;
+            }
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<6>();
+        assert_eq!(BigInt::<4>::choose(14, 7), BigInt::from_i128(3432));
+    }
+
+    #[test]
+    fn fibonacci_largest_fitting() {
+        // the largest n such that F(n) has at most N digits
+        fn largest<const N: usize>() -> u32 {
+            let (mut a, mut b) = (BigInt::<200>::from_i128(0), BigInt::<200>::from_i128(1));
+            let mut n = 0;
+            while b.size() <= N {
+                (a, b) = (b, a + b);
+                n += 1;
+            }
+            assert_eq!(BigInt::<N>::fibonacci(n).to_string(), a.to_string(), "F({})", n);
+            n
+        }
+        assert_eq!(largest::<1>(), 6);
+        assert_eq!(largest::<2>(), 11);
+        assert_eq!(largest::<3>(), 16);
+        assert_eq!(largest::<4>(), 20);
+        assert_eq!(largest::<100>(), 480);
+        assert_eq!(BigInt::<2>::fibonacci(11), BigInt::from_i128(89));
+        assert_eq!(BigInt::<3>::fibonacci(16), BigInt::from_i128(987));
+    }
+
+    #[test]
+    fn fibonacci() {
+        const F: BigInt<100> = BigInt::fibonacci(100);
+        assert_eq!(F, BigInt::from_str("354224848179261915075"));
+
+        let (mut a, mut b) = (0i128, 1i128);
+        for n in 0..=150u32 {
+            assert_eq!(BigIntTest::fibonacci(n), BigInt::from_i128(a), "F({})", n);
+            (a, b) = (b, a + b);
+        }
+    }
+
+    #[test]
+    fn digits_iter() {
+        assert_eq!(BigIntTest::from_i128(0).digits().collect::<Vec<_>>(), [0]);
+        assert_eq!(BigIntTest::from_i128(-120).digits().collect::<Vec<_>>(), [1, 2, 0]);
+        assert_eq!(BigIntTest::from_i128(-120).digits_le().collect::<Vec<_>>(), [0, 2, 1]);
+
+        // a full buffer, a single digit BigInt and both ends of the iterator
+        let full = BigInt::<5>::from_i128(-98765);
+        assert_eq!(full.digits().collect::<Vec<_>>(), [9, 8, 7, 6, 5]);
+        assert_eq!(full.digits_le().len(), 5);
+        assert_eq!(BigInt::<1>::from_i128(-7).digits().collect::<Vec<_>>(), [7]);
+        assert_eq!(BigInt::<1>::from_i128(0).digits_le().collect::<Vec<_>>(), [0]);
+        let x = BigIntTest::from_i128(12345);
+        let mut digits = x.digits();
+        assert_eq!((digits.next(), digits.next_back(), digits.len()), (Some(1), Some(5), 3));
+
+        for x in random_values(7, 100) {
+            let expected: Vec<u8> = x.abs().to_string().bytes().map(|c| c - b'0').collect();
+            assert_eq!(x.digits().collect::<Vec<_>>(), expected);
+            assert!(x.digits_le().eq(expected.into_iter().rev()));
+        }
+    }
+
+    #[test]
+    fn from_digits_be() {
+        assert_eq!(BigIntTest::from_digits_be(false, &[]), BigInt::from_i128(0));
+        assert_eq!(BigIntTest::from_digits_be(true, &[0, 0]).to_string(), "0");
+        assert_eq!(BigIntTest::from_digits_be(true, &[0, 0, 4, 2]), BigInt::from_i128(-42));
+        assert_eq!(BigIntTest::from_digits_be(true, &[0, 0, 4, 2]).size(), 2);
+        assert_eq!(BigInt::<3>::from_digits_be(false, &[0, 0, 9, 9, 9]), BigInt::from_i128(999));
+
+        // leading zeros don't count towards MAX_SIZE, even for a single digit
+        assert_eq!(BigInt::<1>::from_digits_be(true, &[0, 0, 0, 9]), BigInt::from_i128(-9));
+        assert_eq!(BigInt::<1>::from_digits_be(false, &[0; 10]), BigInt::from_i128(0));
+        assert_eq!(BigInt::<1>::from_digits_be(false, &[0; 10]).size(), 1);
+
+        for x in random_values(11, 100) {
+            let digits: Vec<u8> = x.digits().collect();
+            assert_eq!(BigIntTest::from_digits_be(x < 0, &digits), x);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid digit in from_digits_be")]
+    fn from_digits_be_invalid() {
+        BigIntTest::from_digits_be(false, &[1, 10]);
+    }
+
+    #[test]
+    #[should_panic(expected = "number too large for BigInt<MAX_SIZE>")]
+    fn from_digits_be_too_long() {
+        BigInt::<3>::from_digits_be(false, &[1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn default() {
+        assert_eq!(BigInt::<100>::default(), BigInt::from_i128(0));
+
+        #[derive(Default)]
+        struct Counter {
+            total: BigIntTest,
+        }
+        assert!(Counter::default().total.is_zero());
+    }
+
+    #[test]
+    fn mod_inverse() {
+        fn gcd(a: i128, b: i128) -> i128 {
+            if b == 0 { a.abs() } else { gcd(b, a % b) }
+        }
+
+        for m in 2..60i128 {
+            let m1: BigIntTest = BigInt::from_i128(m);
+            for a in -60..60i128 {
+                let inverse = BigIntTest::from_i128(a).mod_inverse(m1);
+                if gcd(a, m) == 1 {
+                    let x = inverse.unwrap();
+                    assert!(x >= 0 && x < m1);
+                    assert_eq!((BigIntTest::from_i128(a) * x).rem_euclid(m1), BigInt::from_i128(1), "{}^-1 mod {}", a, m);
+                } else {
+                    assert_eq!(inverse, None, "{}^-1 mod {}", a, m);
+                }
+            }
+        }
+
+        const P: BigIntTest = BigInt::from_str("1000000007");
+        const INV: Option<BigIntTest> = BigInt::from_i128(2).mod_inverse(P);
+        assert_eq!(INV, Some(BigInt::from_i128(500000004)));
+    }
+
+    #[test]
+    fn power_of_ten() {
+        for x in -20..=2000i128 {
+            let x1: BigIntTest = BigInt::from_i128(x);
+            let expected = x > 0 && 10i128.pow(x.ilog10()) == x;
+            assert_eq!(x1.is_power_of_ten(), expected, "{}", x);
+
+            let mut next = 1i128;
+            while next < x {
+                next *= 10;
+            }
+            assert_eq!(x1.next_power_of_ten(), BigInt::from_i128(next), "{}", x);
+        }
+
+        let ten: BigIntTest = BigInt::from_i128(10);
+        for n in 0..99u32 {
+            let p = ten.pow(n);
+            assert!(p.is_power_of_ten());
+            assert_eq!(p.next_power_of_ten(), p);
+            assert_eq!((p + BigInt::from_i128(1)).next_power_of_ten(), p * ten);
+            assert_eq!((p - BigInt::from_i128(1)).next_power_of_ten(), p);
+        }
+    }
+
+    #[test]
+    fn random_digits() {
+        let mut seed = 99u64;
+        let mut rng = || {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((seed >> 33) % 10) as u8
+        };
+
+        for num_digits in 1..=100 {
+            let x = BigIntTest::random_digits(num_digits, &mut rng);
+            assert_eq!(x.size(), num_digits);
+            assert!(x >= 0);
+            let s = x.to_string();
+            assert_eq!(s.len(), num_digits);
+            assert!(s.bytes().all(|c| c.is_ascii_digit()));
+        }
+
+        // the leading digit is redrawn, not forced
+        let mut digits = [0, 0, 0, 7, 1, 2].into_iter();
+        assert_eq!(BigIntTest::random_digits(3, &mut || digits.next().unwrap()), BigInt::from_i128(712));
+    }
+
+    #[test]
+    fn random_below() {
+        let mut seed = 5u64;
+        let mut rng = || {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((seed >> 33) % 10) as u8
+        };
+
+        for bound in [1i128, 2, 7, 10, 13, 100, 999, 1000, 123456789] {
+            let bound1: BigIntTest = BigInt::from_i128(bound);
+            for _ in 0..200 {
+                let x = BigIntTest::random_below(bound1, &mut rng);
+                assert!(x >= 0 && x < bound1, "{} not below {}", x, bound);
+            }
+        }
+
+        // every value of a small range shows up, roughly equally often
+        let mut counts = [0; 13];
+        for _ in 0..13000 {
+            let x = BigIntTest::random_below(BigInt::from_i128(13), &mut rng);
+            counts[x.to_i128().unwrap() as usize] += 1;
+        }
+        assert!(counts.iter().all(|&count| (800..1200).contains(&count)), "{:?}", counts);
+
+        let big: BigIntTest = BigInt::from_str(&"9".repeat(80));
+        let x = BigIntTest::random_below(big, &mut rng);
+        assert!(x < big && x.size() > 70);
+    }
+
+    #[test]
+    #[should_panic]
+    fn random_below_zero() {
+        BigIntTest::random_below(BigInt::from_i128(0), &mut || 0);
+    }
+
+    #[test]
+    fn div_large_buffer() {
+        // only the significant digits are visited, so a huge buffer gives the same results
+        type Huge = BigInt<10000>;
+        for x in [-98765432109876543i128, -1000, -7, 0, 5, 999, 123456789, i64::MAX as i128] {
+            for y in [-1234567i128, -10, -3, 1, 2, 9, 11, 99991, 1000000007] {
+                let (q, r) = Huge::from_i128(x).div(Huge::from_i128(y));
+                let (q1, r1) = BigInt::<20>::from_i128(x).div(BigInt::from_i128(y));
+                assert_eq!(q, q1.resize::<10000>(), "{} / {}", x, y);
+                assert_eq!(r, r1.resize::<10000>(), "{} % {}", x, y);
+                assert_eq!(q.size(), q1.size());
+                assert_eq!(r.size(), r1.size());
+            }
+        }
+    }
+
+    #[test]
+    fn is_one() {
+        assert!(BigIntTest::from_i128(1).is_one());
+        assert!(!BigIntTest::from_i128(1).is_negative_one());
+        assert!(BigIntTest::from_i128(-1).is_negative_one());
+        assert!(!BigIntTest::from_i128(-1).is_one());
+        for x in [0i128, 2, -2, 10, -10, 11, 101, -1001] {
+            assert!(!BigIntTest::from_i128(x).is_one(), "{}", x);
+            assert!(!BigIntTest::from_i128(x).is_negative_one(), "{}", x);
+        }
+        assert!(BigIntTest::from_str("+0001").is_one());
+        assert!(BigIntTest::from_str("-01").is_negative_one());
+    }
+
+    #[test]
+    fn mul_fast_paths() {
+        let ten: BigIntTest = BigInt::from_i128(10);
+        for x in random_values(17, 60) {
+            for y in [x, BigInt::from_i128(0)] {
+                for special in [BigInt::from_i128(1), BigInt::from_i128(-1), ten, ten.pow(7), -ten.pow(3), ten.pow(40)] {
+                    assert_eq!(y * special, y.mul_schoolbook(special).0, "{} * {}", y, special);
+                    assert_eq!(special * y, y.mul_schoolbook(special).0, "{} * {}", special, y);
+                }
+            }
+        }
+
+        // a power of ten that would overflow still reports it
+        let big: BigInt<5> = BigInt::from_i128(12345);
+        assert_eq!(big.checked_mul(BigInt::from_i128(10)), None);
+        assert_eq!(big.checked_mul(BigInt::from_i128(1)), Some(big));
+        assert_eq!(BigInt::<5>::from_i128(123).checked_mul(BigInt::from_i128(100)), Some(BigInt::from_i128(12300)));
+
+        // products that exactly fill the buffer, and -1 at the ends of the range
+        let max: BigInt<5> = BigInt::from_i128(99999);
+        assert_eq!(max.checked_mul(BigInt::from_i128(-1)), Some(-max));
+        assert_eq!(BigInt::<5>::from_i128(-1).checked_mul(-max), Some(max));
+        assert_eq!(BigInt::<5>::from_i128(9).checked_mul(BigInt::from_i128(10000)), Some(BigInt::from_i128(90000)));
+        assert_eq!(BigInt::<5>::from_i128(10000).checked_mul(BigInt::from_i128(10)), None);
+        assert_eq!(BigInt::<5>::from_i128(0).checked_mul(BigInt::from_i128(10000)), Some(BigInt::from_i128(0)));
+        assert_eq!(BigInt::<1>::from_i128(-9).checked_mul(BigInt::from_i128(-1)), Some(BigInt::from_i128(9)));
+        assert_eq!(BigInt::<1>::from_i128(1).checked_mul(BigInt::from_i128(-7)), Some(BigInt::from_i128(-7)));
+        assert_eq!((BigInt::<1>::from_i128(0) * BigInt::from_i128(-1)).to_string(), "0");
+    }
+
+    #[test]
+    fn from_bool() {
+        const T: BigIntTest = BigInt::from_bool(true);
+        const F: BigIntTest = BigInt::from_bool(false);
+        assert_eq!(T, BigInt::from_i128(1));
+        assert_eq!(F, BigInt::from_i128(0));
+        assert_eq!(BigIntTest::from(true), T);
+        assert_eq!(BigIntTest::from(false), F);
+
+        let evens: BigIntTest = (1..=10).map(|x| BigInt::from(x % 2 == 0)).sum();
+        assert_eq!(evens, BigInt::from_i128(5));
+    }
+
+    #[test]
+    fn max_min_consts() {
+        type I5 = BigInt<5>;
+        assert_eq!(I5::MAX, BigInt::from_i128(99999));
+        assert_eq!(I5::MIN, BigInt::from_i128(-99999));
+        assert_eq!(I5::MAX.size(), 5);
+        assert_eq!(I5::MAX.checked_add(BigInt::from_i128(1)), None);
+        assert_eq!(I5::MIN.checked_sub(BigInt::from_i128(1)), None);
+        assert_eq!(I5::MAX.checked_add(BigInt::from_i128(-1)), Some(BigInt::from_i128(99998)));
+        assert_eq!(-I5::MAX, I5::MIN);
+        assert_eq!(BigIntTest::MAX.to_string(), "9".repeat(100));
+    }
+
+    #[test]
+    fn trailing_zeros10() {
+        let factorial_25: BigIntTest = (1..=25).map(BigInt::from_i128).product();
+        assert_eq!(factorial_25.trailing_zeros10(), 6);
+        let factorial_100: BigInt<200> = (1..=100).map(BigInt::from_i128).product();
+        assert_eq!(factorial_100.trailing_zeros10(), 24);
+
+        assert_eq!(BigIntTest::from_i128(0).trailing_zeros10(), 0);
+        for x in [1i128, -7, 10, -300, 1010, 123000000, -5000000000000] {
+            let mut expected = 0;
+            while x % 10i128.pow(expected + 1) == 0 {
+                expected += 1;
+            }
+            assert_eq!(BigIntTest::from_i128(x).trailing_zeros10(), expected as usize, "{}", x);
+        }
+    }
+
+    #[test]
+    fn pow10() {
+        const P: BigIntTest = BigInt::pow10(3);
+        assert_eq!(P, BigInt::from_i128(1000));
+        assert_eq!(BigIntTest::pow10(0), BigInt::from_i128(1));
+        let ten: BigIntTest = BigInt::from_i128(10);
+        for n in 0..100 {
+            assert_eq!(BigIntTest::pow10(n), ten.pow(n as u32));
+        }
+        assert_eq!(BigInt::<5>::pow10(4), BigInt::from_i128(10000));
+    }
+
+    #[test]
+    #[should_panic]
+    fn pow10_too_large() {
+        BigInt::<5>::pow10(5);
+    }
+
+    #[test]
+    fn reduce() {
+        const R: (BigIntTest, BigIntTest) = BigInt::reduce(BigInt::from_i128(6), BigInt::from_i128(-4));
+        assert_eq!(R, (BigInt::from_i128(-3), BigInt::from_i128(2)));
+        assert_eq!(
+            BigIntTest::reduce(BigInt::from_i128(0), BigInt::from_i128(-5)),
+            (BigInt::from_i128(0), BigInt::from_i128(1))
+        );
+
+        for num in -30..=30i128 {
+            for den in (-30..=30i128).filter(|&den| den != 0) {
+                let (n, d) = BigIntTest::reduce(BigInt::from_i128(num), BigInt::from_i128(den));
+                assert!(d > 0);
+                assert!(n.gcd(d).is_one(), "{}/{}", num, den);
+                assert_eq!(n * BigInt::from_i128(den), d * BigInt::from_i128(num), "{}/{}", num, den);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn reduce_zero_denominator() {
+        BigIntTest::reduce(BigInt::from_i128(1), BigInt::from_i128(0));
+    }
+
+    #[test]
+    fn add_in_place() {
+        let mut accumulator: BigIntTest = BigInt::from_i128(0);
+        let mut expected: BigIntTest = BigInt::from_i128(0);
+        for x in random_values(23, 90) {
+            accumulator.add_in_place(&x);
+            expected += x;
+            assert_eq!(accumulator, expected);
+            assert_eq!(accumulator.size(), expected.size());
+        }
+
+        for x in -60..=60i128 {
+            for y in -60..=60i128 {
+                let mut x1: BigIntTest = BigInt::from_i128(x);
+                x1.add_in_place(&BigInt::from_i128(y));
+                assert_eq!(x1, BigInt::from_i128(x + y), "{} + {}", x, y);
+            }
+        }
+
+        // carries that exactly fill the buffer, and sums that cancel to zero
+        let mut x: BigInt<3> = BigInt::from_i128(-500);
+        x.add_in_place(&BigInt::from_i128(-499));
+        assert_eq!(x, BigInt::from_i128(-999));
+        let mut x = BigInt::<5>::MAX;
+        x.add_in_place(&BigInt::MIN);
+        assert_eq!(x.to_string(), "0");
+        assert_eq!(x.size(), 1);
+        let mut x: BigInt<1> = BigInt::from_i128(4);
+        x.add_in_place(&BigInt::from_i128(5));
+        assert_eq!(x, BigInt::from_i128(9));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "addition overflow")]
+    fn add_in_place_overflow() {
+        let mut x = BigInt::<3>::MAX;
+        x.add_in_place(&BigInt::from_i128(1));
+    }
+
+    #[test]
+    fn try_from_str_ref() {
+        use super::ParseBigIntError;
+
+        assert_eq!(BigIntTest::try_from("-12345"), Ok(BigInt::from_i128(-12345)));
+        assert_eq!(BigIntTest::try_from("+007"), Ok(BigInt::from_i128(7)));
+        let x: Result<BigInt<3>, _> = "999".try_into();
+        assert_eq!(x, Ok(BigInt::from_i128(999)));
+
+        assert_eq!(BigIntTest::try_from(""), Err(ParseBigIntError::Empty));
+        assert_eq!(BigIntTest::try_from("12x45"), Err(ParseBigIntError::InvalidDigit('x')));
+        assert_eq!(BigInt::<3>::try_from("1000"), Err(ParseBigIntError::Overflow));
+    }
+
+    #[test]
+    fn wrapping() {
+        type I3 = BigInt<3>;
+        const X: I3 = BigInt::from_i128(999).wrapping_mul(BigInt::from_i128(2));
+        assert_eq!(X, BigInt::from_i128(998));
+        assert_eq!(I3::from_i128(999).wrapping_add(BigInt::from_i128(1)), BigInt::from_i128(0));
+        assert_eq!(I3::from_i128(-999).wrapping_add(BigInt::from_i128(-1)).to_string(), "0");
+        assert_eq!(I3::from_i128(-999).wrapping_mul(BigInt::from_i128(2)), BigInt::from_i128(-998));
+        assert_eq!(I3::from_i128(-500).wrapping_mul(BigInt::from_i128(-2)).to_string(), "0");
+
+        for x in -999..=999i128 {
+            for y in [-999i128, -123, -2, -1, 0, 1, 7, 500, 999] {
+                let (x1, y1) = (I3::from_i128(x), I3::from_i128(y));
+                let sum = x + y;
+                let product = x * y;
+                assert_eq!(x1.wrapping_add(y1), BigInt::from_i128(sum.signum() * (sum.abs() % 1000)), "{} + {}", x, y);
+                assert_eq!(x1.wrapping_mul(y1), BigInt::from_i128(product.signum() * (product.abs() % 1000)), "{} * {}", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn nth_root() {
+        const R: BigIntTest = BigInt::from_i128(1000).nth_root(3);
+        assert_eq!(R, BigInt::from_i128(10));
+
+        for x in (0..2000i128).chain([123456789, i64::MAX as i128, i128::MAX]) {
+            let x1: BigIntTest = BigInt::from_i128(x);
+            assert_eq!(x1.nth_root(1), x1);
+            assert_eq!(x1.nth_root(2), x1.isqrt());
+            for n in 2..12u32 {
+                let root = x1.nth_root(n);
+                let next = root + BigInt::from_i128(1);
+                assert!(root.pow(n) <= x1, "{}^(1/{})", x, n);
+                assert!(next.pow(n) > x1, "{}^(1/{})", x, n);
+            }
+        }
+
+        assert_eq!(BigIntTest::from_i128(-27).nth_root(3), BigInt::from_i128(-3));
+        assert_eq!(BigIntTest::from_i128(-30).nth_root(3), BigInt::from_i128(-3));
+        assert_eq!(BigIntTest::from_i128(5).nth_root(u32::MAX), BigInt::from_i128(1));
+
+        let big = BigIntTest::from_str(&"9".repeat(100));
+        assert_eq!(big.nth_root(2), BigInt::from_str(&"9".repeat(50)));
+        assert_eq!(big.nth_root(50), BigInt::from_i128(99));
+        assert_eq!(big.nth_root(100), BigInt::from_i128(9));
+    }
+
+    #[test]
+    #[should_panic]
+    fn nth_root_zero() {
+        BigIntTest::from_i128(8).nth_root(0);
+    }
+
+    #[test]
+    fn nth_root_small_max_size() {
+        let root = |x: i128, n: u32| (0..=x.abs()).filter(|r| r.pow(n) <= x.abs()).max().unwrap() * x.signum();
+        assert_eq!(BigInt::<1>::from_i128(8).nth_root(3), BigInt::from_i128(2));
+        for n in 1..=4 {
+            for x in -9..=9 {
+                if x >= 0 || n % 2 == 1 {
+                    assert_eq!(BigInt::<1>::from_i128(x).nth_root(n), BigInt::from_i128(root(x, n)), "{}^(1/{})", x, n);
+                }
+            }
+            for x in -99..=99 {
+                if x >= 0 || n % 2 == 1 {
+                    assert_eq!(BigInt::<2>::from_i128(x).nth_root(n), BigInt::from_i128(root(x, n)), "{}^(1/{})", x, n);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn nth_root_even_negative() {
+        BigIntTest::from_i128(-16).nth_root(4);
+    }
+
+    #[test]
+    fn const_clone() {
+        const X: BigIntTest = BigInt::from_str("-1234567890");
+        const Y: BigIntTest = X.const_clone();
+        assert_eq!(Y, X);
+        assert_eq!(Y.is_negative, X.is_negative);
+        assert_eq!(Y.current_size, X.current_size);
+        assert_eq!(Y.digits, X.digits);
+
+        let zero: BigIntTest = BigInt::from_i128(0);
+        assert!(!zero.const_clone().is_negative);
+        assert_eq!(zero.const_clone().current_size, 1);
+    }
+
+    #[test]
+    fn to_f64() {
+        for x in [0i128, 1, -1, 42, -123456789, 1 << 53, (1 << 53) + 1, -(1 << 60) - 3, i64::MAX as i128, i128::MAX, i128::MIN] {
+            assert_eq!(BigIntTest::from_i128(x).to_f64(), x as f64, "{}", x);
+        }
+        assert!(BigIntTest::from_i128(0).to_f64().is_sign_positive());
+        assert_eq!(BigInt::<1>::from_i128(-9).to_f64(), -9.0);
+
+        for x in random_values(31, 100) {
+            let s = x.to_string();
+            assert_eq!(x.to_f64(), s.parse::<f64>().unwrap(), "{}", s);
+        }
+
+        // (2^53 + 1) * 2^100 is halfway between two f64s, so a digit
+        // past the first 40 decides the rounding
+        let halfway = BigIntTest::from_str("11417981541647680316116887983825362587765178368");
+        let above = halfway + BigInt::from_i128(1);
+        assert_eq!(halfway.to_f64(), 1.141798154164768e46);
+        assert_eq!(above.to_f64(), 1.1417981541647682e46);
+
+        type I400 = BigInt<400>;
+        assert_eq!(I400::MAX.to_f64(), f64::INFINITY);
+        assert_eq!(I400::MIN.to_f64(), f64::NEG_INFINITY);
+        assert_eq!(I400::pow10(308).to_f64(), 1e308);
+
+        // f64::MAX + 2^970 is halfway to 2^1024 and rounds to infinity
+        let max = I400::from_str(&format!("{:.0}", f64::MAX));
+        let half_ulp = I400::from_i128(2).pow(970);
+        assert_eq!(max.to_f64(), f64::MAX);
+        assert_eq!((max + half_ulp - I400::from_i128(1)).to_f64(), f64::MAX);
+        assert_eq!((max + half_ulp).to_f64(), f64::INFINITY);
+        assert_eq!((-max - half_ulp).to_f64(), f64::NEG_INFINITY);
+        assert_eq!(BigInt::<308>::MAX.to_f64(), 1e308);
+        assert_eq!(BigInt::<309>::MAX.to_f64(), f64::INFINITY);
+    }
+
+    #[test]
+    fn from_f64() {
+        use super::FromF64Error;
+
+        for x in [0.0, -0.0, 1.0, -1.0, 0.5, -0.99, 2.5, -2.5, 123456.789, -9007199254740993.0, 1e30, -1.5e38, f64::MIN_POSITIVE, 5e-324] {
+            assert_eq!(BigIntTest::from_f64(x), Ok(BigInt::from_i128(x as i128)), "{}", x);
+        }
+        assert_eq!(BigIntTest::from_f64(-0.0).unwrap().to_string(), "0");
+        assert_eq!(BigIntTest::from_f64(-0.5).unwrap().to_string(), "0");
+
+        for x in random_values(41, 100) {
+            let f = x.to_f64();
+            assert_eq!(BigIntTest::from_f64(f).unwrap().to_f64(), f);
+        }
+        assert_eq!(BigInt::<400>::from_f64(1e300).unwrap().to_string().len(), 301);
+
+        // f64::MAX has exactly 309 digits
+        assert_eq!(BigInt::<309>::from_f64(f64::MAX).unwrap().to_f64(), f64::MAX);
+        assert_eq!(BigInt::<309>::from_f64(-f64::MAX).unwrap().to_f64(), -f64::MAX);
+        assert_eq!(BigInt::<308>::from_f64(f64::MAX), Err(FromF64Error::OutOfRange));
+
+        assert_eq!(BigIntTest::from_f64(f64::NAN), Err(FromF64Error::NaN));
+        assert_eq!(BigIntTest::from_f64(f64::INFINITY), Err(FromF64Error::Infinite));
+        assert_eq!(BigIntTest::from_f64(f64::NEG_INFINITY), Err(FromF64Error::Infinite));
+        assert_eq!(BigInt::<5>::from_f64(100000.0), Err(FromF64Error::OutOfRange));
+        assert_eq!(BigInt::<5>::from_f64(-99999.9), Ok(BigInt::from_i128(-99999)));
+        assert_eq!(BigInt::<1>::from_f64(9.99), Ok(BigInt::from_i128(9)));
+        assert_eq!(BigInt::<1>::from_f64(-10.0), Err(FromF64Error::OutOfRange));
+
+        assert_eq!(FromF64Error::NaN.to_string(), "cannot convert NaN to BigInt");
+    }
+
+    #[test]
+    fn bit_length() {
+        assert_eq!(BigIntTest::from_i128(0).bit_length(), 0);
+        for x in (1..=5000i128).chain([i64::MAX as i128, i128::MAX]) {
+            assert_eq!(BigIntTest::from_i128(x).bit_length(), x.ilog2() + 1, "{}", x);
+            assert_eq!(BigIntTest::from_i128(-x).bit_length(), x.ilog2() + 1, "{}", -x);
+        }
+        assert_eq!(BigIntTest::from_i128(2).pow(300).bit_length(), 301);
+    }
+
+    #[test]
+    fn is_divisible_by() {
+        for x in -300..=300i128 {
+            let x1: BigIntTest = BigInt::from_i128(x);
+            for y in (-30..=30i128).filter(|&y| y != 0).chain([100, 101, 1001, -999]) {
+                assert_eq!(x1.is_divisible_by(BigInt::from_i128(y)), x % y == 0, "{} | {}", y, x);
+            }
+        }
+
+        let big: BigIntTest = BigInt::from_str(&"9".repeat(90));
+        assert!(big.is_divisible_by(BigInt::from_i128(9)));
+        assert!(big.is_divisible_by(BigInt::from_str(&"9".repeat(45))));
+        assert!(!(big + BigInt::from_i128(1)).is_divisible_by(BigInt::from_i128(3)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn is_divisible_by_zero() {
+        BigIntTest::from_i128(10).is_divisible_by(BigInt::from_i128(0));
+    }
+
+    #[test]
+    fn powers() {
+        let powers: Vec<BigInt<5>> = BigInt::powers(BigInt::from_i128(2)).collect();
+        assert_eq!(powers.len(), 17);
+        assert_eq!(powers.last(), Some(&BigInt::from_i128(65536)));
+        for (n, power) in powers.iter().enumerate() {
+            assert_eq!(*power, BigInt::from_i128(1 << n));
+        }
+
+        let powers: Vec<BigIntTest> = BigInt::powers(BigInt::from_i128(-3)).take(6).collect();
+        assert_eq!(powers, [1, -3, 9, -27, 81, -243].map(BigInt::from_i128));
+        assert_eq!(BigInt::<5>::powers(BigInt::from_i128(10)).count(), 5);
+
+        // bases whose powers repeat end after each distinct power
+        assert_eq!(BigInt::<5>::powers(BigInt::from_i128(0)).collect::<Vec<_>>(), [1, 0].map(BigInt::from_i128));
+        assert_eq!(BigInt::<5>::powers(BigInt::from_i128(1)).collect::<Vec<_>>(), [1].map(BigInt::from_i128));
+        assert_eq!(BigInt::<5>::powers(BigInt::from_i128(-1)).collect::<Vec<_>>(), [1, -1].map(BigInt::from_i128));
+        assert_eq!(BigInt::<1>::powers(BigInt::from_i128(-1)).count(), 2);
+
+        // the last power exactly fills the buffer
+        assert_eq!(BigInt::<5>::powers(BigInt::from_i128(-9)).last(), Some(BigInt::from_i128(-59049)));
+        assert_eq!(BigInt::<1>::powers(BigInt::from_i128(3)).collect::<Vec<_>>(), [1, 3, 9].map(BigInt::from_i128));
+    }
+
+    #[test]
+    fn checked_div() {
+        const Q: Option<(BigIntTest, BigIntTest)> = BigInt::from_i128(-17).checked_div(BigInt::from_i128(5));
+        assert_eq!(Q, Some((BigInt::from_i128(-3), BigInt::from_i128(-2))));
+        assert_eq!(BigIntTest::from_i128(17).checked_div(BigInt::from_i128(0)), None);
+        assert_eq!(BigIntTest::from_i128(0).checked_div(BigInt::from_i128(0)), None);
+        for x in -50..=50i128 {
+            for y in (-7..=7i128).filter(|&y| y != 0) {
+                let (x1, y1) = (BigIntTest::from_i128(x), BigIntTest::from_i128(y));
+                assert_eq!(x1.checked_div(y1), Some((BigInt::from_i128(x / y), BigInt::from_i128(x % y))));
+            }
+        }
     }
 
     #[test]
-    fn from_negative() {
-        const STR: &str = "-123456789123456789123456789123456789";
-        const X: BigIntTest = BigInt::from_str(STR);
-        const DIGITS: [u8; X.max_size()] = { 
-            let mut result =  [0u8; X.max_size()]; 
+    fn overflowing() {
+        type I3 = BigInt<3>;
+        const X: (I3, bool) = BigInt::from_i128(999).overflowing_add(BigInt::from_i128(1));
+        assert_eq!(X, (BigInt::from_i128(0), true));
+        assert_eq!(I3::from_i128(998).overflowing_add(BigInt::from_i128(1)), (BigInt::from_i128(999), false));
+        assert_eq!(I3::from_i128(999).overflowing_mul(BigInt::from_i128(2)), (BigInt::from_i128(998), true));
+        assert_eq!(I3::from_i128(-333).overflowing_mul(BigInt::from_i128(3)), (BigInt::from_i128(-999), false));
 
-            let digits = BigIntTest::DEFAULT.max_size() - STR.len() + 1;
-            let mut i = digits;
-            while i < BigIntTest::DEFAULT.max_size() {
-                result[i as usize] = ((i - digits) % 9 + 1) as u8;
-                i+=1;
+        for x in -999..=999i128 {
+            for y in [-999i128, -500, -1, 0, 1, 2, 499, 999] {
+                let (x1, y1) = (I3::from_i128(x), I3::from_i128(y));
+                assert_eq!(x1.overflowing_add(y1), (x1.wrapping_add(y1), (x + y).abs() > 999), "{} + {}", x, y);
+                assert_eq!(x1.overflowing_mul(y1), (x1.wrapping_mul(y1), (x * y).abs() > 999), "{} * {}", x, y);
             }
+        }
+    }
 
-            result
-        };
+    #[test]
+    fn max_min_value() {
+        const MAX: BigInt<7> = BigInt::max_value();
+        const MIN: BigInt<7> = BigInt::min_value();
+        assert_eq!(MAX, BigInt::<7>::MAX);
+        assert_eq!(MIN, BigInt::<7>::MIN);
+        assert_eq!(BigIntTest::max_value(), BigIntTest::MAX);
+        assert_eq!(BigIntTest::min_value(), BigIntTest::MIN);
+        assert_eq!(MAX.size(), 7);
+        assert_eq!(MIN.size(), 7);
+    }
 
-        const EXPECTED: BigIntTest = BigInt { is_negative: true, digits:DIGITS, current_size: STR.len() - 1};
-        assert_eq!(X, EXPECTED);
+    #[test]
+    fn div_rem_small() {
+        for x in (-500..=500i128).chain([i64::MIN as i128, i128::MAX]) {
+            let x1: BigIntTest = BigInt::from_i128(x);
+            for d in [1u32, 2, 3, 7, 10, 99, 1000, 65537, u32::MAX] {
+                let (q, r) = x1.div_rem_small(d);
+                assert_eq!(q, x1.div(BigInt::from_i128(d as i128)).0, "{} / {}", x, d);
+                assert_eq!(r as i128, x.abs() % d as i128, "{} % {}", x, d);
+            }
+        }
+
+        let d: BigIntTest = BigInt::from_i128(123456789);
+        for x in random_values(47, 100) {
+            let (q, r) = x.div_rem_small(123456789);
+            let r: BigIntTest = BigInt::from_i128(r as i128);
+            assert_eq!(q * d + if x < 0 { -r } else { r }, x);
+        }
+
+        // divisors above the value, a negative zero quotient and full buffers
+        assert_eq!(BigInt::<1>::from_i128(9).div_rem_small(10), (BigInt::from_i128(0), 9));
+        assert_eq!(BigInt::<1>::from_i128(-9).div_rem_small(2), (BigInt::from_i128(-4), 1));
+        let (q, r) = BigInt::<1>::from_i128(-3).div_rem_small(u32::MAX);
+        assert_eq!((q.to_string(), q.size(), r), ("0".to_string(), 1, 3));
+        let max = 10u128.pow(20) - 1;
+        let expected = (BigInt::from_u128(max / u32::MAX as u128), (max % u32::MAX as u128) as u32);
+        assert_eq!(BigInt::<20>::MAX.div_rem_small(u32::MAX), expected);
+        assert_eq!(BigInt::<5>::MIN.div_rem_small(1), (BigInt::MIN, 0));
     }
 
     #[test]
-    fn from_i128() {
-        {
-            const NUM: i128 = 1234567891234567891;
-            const X: BigIntTest = BigInt::from_i128(NUM);
-            const Y: BigIntTest = BigInt::from_str("1234567891234567891");
-            assert_eq!(X, Y);
+    #[should_panic]
+    fn div_rem_small_zero() {
+        BigIntTest::from_i128(5).div_rem_small(0);
+    }
+
+    #[test]
+    fn isqrt_rem() {
+        let two: BigIntTest = BigInt::from_i128(2);
+        let one: BigIntTest = BigInt::from_i128(1);
+        let values = (0..3000).map(BigInt::from_i128).chain(random_values(53, 100).map(|x| x.abs()));
+        for x in values {
+            let (root, rem) = x.isqrt_rem();
+            assert_eq!(root * root + rem, x);
+            assert!(rem >= 0 && rem < two * root + one, "{}", x);
         }
 
-        {
-            const NUM: i128 = -1234567891234567891;
-            const X: BigIntTest = BigInt::from_i128(NUM);
-            const Y: BigIntTest = BigInt::from_str("-1234567891234567891");
-            assert_eq!(X, Y);
+        // just below a square the remainder is largest, at a square it is zero
+        for k in [1i128, 9, 99, 316, 9999, 99999999999] {
+            let x: BigIntTest = BigInt::from_i128(k * k - 1);
+            assert_eq!(x.isqrt_rem(), (BigInt::from_i128(k - 1), BigInt::from_i128(2 * k - 2)));
+            assert_eq!((x + one).isqrt_rem(), (BigInt::from_i128(k), BigInt::from_i128(0)));
+        }
+        assert_eq!(BigInt::<5>::MAX.isqrt_rem(), (BigInt::from_i128(316), BigInt::from_i128(143)));
+        for x in 0..10i128 {
+            let r = (0..=x).filter(|r| r * r <= x).max().unwrap();
+            assert_eq!(BigInt::<1>::from_i128(x).isqrt_rem(), (BigInt::from_i128(r), BigInt::from_i128(x - r * r)));
         }
     }
 
     #[test]
-    fn less() {
-        for i in -1000..1000 {
-            for j in -1000..1000 {
-                let i_str = i.to_string();
-                let j_str = j.to_string();
-                let i1: BigIntTest = BigInt::from_str(&i_str);
-                let j1: BigIntTest = BigInt::from_str(&j_str);
-                let result = i < j;
-                let expected = i1 < j1; 
-                assert_eq!(result, expected);
+    #[should_panic]
+    fn isqrt_rem_negative() {
+        BigIntTest::from_i128(-4).isqrt_rem();
+    }
+
+    #[test]
+    fn mul_mod() {
+        for m in [1i128, 2, 7, 10, 97, -13, 1000003] {
+            let m1: BigIntTest = BigInt::from_i128(m);
+            for x in [-123456789i128, -1000, -7, -1, 0, 1, 5, 96, 1000, 987654321] {
+                for y in [-999999i128, -3, 0, 2, 13, 424242] {
+                    let expected = (x * y).rem_euclid(m.abs());
+                    assert_eq!(BigIntTest::from_i128(x).mul_mod(BigInt::from_i128(y), m1), BigInt::from_i128(expected), "{} * {} mod {}", x, y, m);
+                }
             }
         }
+
+        // the operands alone would overflow a BigInt<20>, their residues don't
+        type I20 = BigInt<20>;
+        let big = I20::from_str("99999999999999999999");
+        let m = I20::from_i128(1000000007);
+        let residue = 99999999999999999999i128 % 1000000007;
+        assert_eq!(big.mul_mod(big, m), BigInt::from_i128(residue * residue % 1000000007));
     }
 
     #[test]
-    fn greater() {
-        for i in -1000..1000 {
-            for j in -1000..1000 {
-                let i_str = i.to_string();
-                let j_str = j.to_string();
-                let i1: BigIntTest = BigInt::from_str(&i_str);
-                let j1: BigIntTest = BigInt::from_str(&j_str);
-                let result = i > j;
-                let expected = i1 > j1;
-                assert_eq!(result, expected);
-            }
+    fn digit_at() {
+        const X: BigIntTest = BigInt::from_str("-9081726354");
+        const LAST: u8 = X.digit_at(0);
+        assert_eq!(LAST, 4);
+        let expected = [4, 5, 3, 6, 2, 7, 1, 8, 0, 9];
+        for (place, &digit) in expected.iter().enumerate() {
+            assert_eq!(X.digit_at(place), digit);
         }
+        for place in [10, 11, 99, 100, usize::MAX] {
+            assert_eq!(X.digit_at(place), 0);
+        }
+        assert_eq!(BigIntTest::from_i128(0).digit_at(0), 0);
+        assert_eq!(BigInt::<3>::from_i128(999).digit_at(2), 9);
+        assert_eq!(BigInt::<3>::from_i128(999).digit_at(3), 0);
     }
 
     #[test]
-    fn equal() {
-        for i in -1000..1000 {
-            for j in -1000..1000 {
-                let i_str = i.to_string();
-                let j_str = j.to_string();
-                let i1: BigIntTest = BigInt::from_str(&i_str);
-                let j1: BigIntTest = BigInt::from_str(&j_str);
-                let result = i == j;
-                let expected = i1 == j1;
-                assert_eq!(result, expected);
-            }
+    fn with_digit() {
+        const X: BigIntTest = BigInt::DEFAULT.with_digit(0, 4).with_digit(3, 7).with_digit(1, 2);
+        assert_eq!(X, BigInt::from_i128(7024));
+        assert_eq!(X.size(), 4);
+
+        let mut y = BigIntTest::DEFAULT;
+        for (place, digit) in "1234567890123456789".bytes().rev().enumerate() {
+            y = y.with_digit(place, digit - b'0');
         }
+        assert_eq!(y, BigInt::from_i128(1234567890123456789));
+
+        let x = BigIntTest::from_i128(-123);
+        assert_eq!(x.with_digit(5, 9), BigInt::from_i128(-900123));
+        assert_eq!(x.with_digit(5, 9).size(), 6);
+        assert_eq!(x.with_digit(5, 0), x);
+        assert_eq!(x.with_digit(2, 0), BigInt::from_i128(-23));
+        assert_eq!(x.with_digit(2, 0).size(), 2);
+        assert_eq!(BigIntTest::from_i128(-5).with_digit(0, 0).to_string(), "0");
+        assert_eq!(BigInt::<3>::from_i128(5).with_digit(2, 1), BigInt::from_i128(105));
     }
 
     #[test]
-    fn add(){
-        {
-            const STR1: &str = "-1";
-            const STR2: &str = "999";
-            const RESULT: &str = "998";
-            const X: BigIntTest = BigInt::from_str(STR1);
-            const Y: BigIntTest = BigInt::from_str(STR2);
-            
-            const Z: BigIntTest = X.add(Y);
+    #[should_panic]
+    fn with_digit_invalid() {
+        BigIntTest::from_i128(5).with_digit(0, 10);
+    }
 
-            const EXPECTED: BigIntTest = BigInt::from_str(RESULT);
-            assert_eq!(Z, EXPECTED);
+    #[test]
+    fn reverse_digits() {
+        const X: BigIntTest = BigInt::from_i128(1200).reverse_digits();
+        assert_eq!(X, BigInt::from_i128(21));
+        assert_eq!(X.size(), 2);
+        for x in [0i128, 7, -7, 11, 12321, -1234321, 1000000000000000000001] {
+            assert_eq!(BigIntTest::from_i128(x).reverse_digits(), BigInt::from_i128(x));
         }
-        {
-            const STR1: &str = "-123456789123456789123456789123456789";
-            const STR2: &str = "-987654321987654321987654321987654321";
-            const RESULT: &str = "-1111111111111111111111111111111111110";
-            const X: BigIntTest = BigInt::from_str(STR1);
-            const Y: BigIntTest = BigInt::from_str(STR2);
+        assert_eq!(BigIntTest::from_i128(-123456).reverse_digits(), BigInt::from_i128(-654321));
+        assert_eq!(BigIntTest::from_i128(-10).reverse_digits(), BigInt::from_i128(-1));
 
-            const Z: BigIntTest = X.add(Y);
+        // full buffers and single digits
+        assert_eq!(BigInt::<5>::MIN.reverse_digits(), BigInt::MIN);
+        assert_eq!(BigInt::<5>::from_i128(-12340).reverse_digits(), BigInt::from_i128(-4321));
+        assert_eq!(BigInt::<5>::from_i128(10000).reverse_digits().size(), 1);
+        assert_eq!(BigInt::<1>::from_i128(-7).reverse_digits(), BigInt::from_i128(-7));
 
-            const EXPECTED: BigIntTest = BigInt::from_str(RESULT);
-            assert_eq!(Z, EXPECTED);
+        for x in random_values(59, 100) {
+            let reversed: String = x.abs().to_string().chars().rev().collect();
+            let expected: BigIntTest = BigInt::from_str(&reversed);
+            assert_eq!(x.reverse_digits(), if x < 0 { -expected } else { expected });
         }
     }
 
     #[test]
-    fn add_2() {
-        // positive + positive
-        for x in -1000..=1000 {
-            for y in -1000..=1000 {
+    fn is_palindrome() {
+        assert!(BigIntTest::from_i128(12321).is_palindrome());
+        assert!(!BigIntTest::from_i128(1234).is_palindrome());
+        assert!(BigIntTest::from_i128(0).is_palindrome());
+        assert!(BigIntTest::from_i128(-1221).is_palindrome());
+        assert!(!BigIntTest::from_i128(10).is_palindrome());
 
-                let x_str = x.to_string();
-                let y_str = y.to_string();
-                let result = (x + y).to_string();
-                let x1: BigIntTest = BigInt::from_str(&x_str);
-                let y1: BigIntTest = BigInt::from_str(&y_str);
-                let result1: BigIntTest = x1 + y1;
-                let expected: BigIntTest = BigInt::from_str(&result);
-                assert_eq!(result1, expected, "{}", format!("{} + {} = {}", x, y, result));
+        for x in 0..3000i128 {
+            let s = x.to_string();
+            let expected = s.chars().rev().collect::<String>() == s;
+            assert_eq!(BigIntTest::from_i128(x).is_palindrome(), expected, "{}", x);
+            assert_eq!(BigIntTest::from_i128(x).is_palindrome(), BigIntTest::from_i128(x).reverse_digits() == BigInt::from_i128(x));
+        }
+    }
+
+    #[test]
+    fn widening_mul() {
+        type I3 = BigInt<3>;
+        const P: BigInt<6> = BigInt::<3>::from_i128(999).widening_mul(BigInt::from_i128(-999));
+        assert_eq!(P, BigInt::from_i128(-998001));
+
+        for x in [-999i128, -500, -1, 0, 7, 123, 999] {
+            for y in [-999i128, -10, 0, 1, 456, 999] {
+                let p: BigInt<6> = I3::from_i128(x).widening_mul(BigInt::from_i128(y));
+                assert_eq!(p, BigInt::from_i128(x * y), "{} * {}", x, y);
             }
         }
+
+        let x = BigIntTest::MAX;
+        let square: BigInt<200> = x.widening_mul(x);
+        // (10^100 - 1)^2 = 10^200 - 2 * 10^100 + 1
+        assert_eq!(square.to_string(), format!("{}8{}1", "9".repeat(99), "0".repeat(99)));
     }
 
     #[test]
-    fn sub(){
-        {
-            const STR2: &str = "987654321987654321987654321987654321";
-            const STR1: &str = "123456789123456789123456789123456789";
+    #[should_panic]
+    fn widening_mul_too_small() {
+        let _: BigInt<5> = BigInt::<3>::from_i128(999).widening_mul(BigInt::from_i128(999));
+    }
 
-            const RESULT: &str = "864197532864197532864197532864197532";
+    #[test]
+    fn from_raw_digits() {
+        const X: BigInt<6> = BigInt::from_raw_digits(true, [0, 0, 1, 2, 0, 3]);
+        assert_eq!(X, BigInt::from_i128(-1203));
+        assert_eq!(X.size(), 4);
 
-            const X: BigIntTest = BigInt::from_str(STR1);
-            const Y: BigIntTest = BigInt::from_str(STR2);
-            const Z: BigIntTest = Y.sub(X);
+        let full = BigInt::<6>::from_raw_digits(false, [9, 8, 7, 6, 5, 4]);
+        assert_eq!(full, BigInt::from_i128(987654));
+        assert_eq!(full.size(), 6);
 
-            const EXPECTED: BigIntTest = BigInt::from_str(RESULT);
-            assert_eq!(Z, EXPECTED);
+        let zero = BigInt::<6>::from_raw_digits(true, [0; 6]);
+        assert_eq!(zero.size(), 1);
+        assert_eq!(zero.to_string(), "0");
+
+        assert_eq!(BigInt::<1>::from_raw_digits(true, [9]), BigInt::from_i128(-9));
+        assert_eq!(BigInt::<1>::from_raw_digits(true, [0]).to_string(), "0");
+
+        for x in random_values(61, 100) {
+            let y = BigIntTest::from_raw_digits(x < 0, x.digits);
+            assert_eq!(y, x);
+            assert_eq!(y.size(), x.size());
         }
-        {
-            const STR2: &str = "-987654321987654321987654321987654321";
-            const STR1: &str = "-123456789123456789123456789123456789";
+    }
 
-            const RESULT: &str = "-864197532864197532864197532864197532";
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn from_raw_digits_invalid() {
+        BigInt::<3>::from_raw_digits(false, [1, 10, 0]);
+    }
 
-            const X: BigIntTest = BigInt::from_str(STR1);
-            const Y: BigIntTest = BigInt::from_str(STR2);
-            const Z: BigIntTest = Y.sub(X);
+    #[test]
+    fn as_significant_slice() {
+        assert_eq!(BigIntTest::from_i128(0).as_significant_slice(), [0]);
+        assert_eq!(BigIntTest::from_i128(-4050).as_significant_slice(), [4, 0, 5, 0]);
+        assert_eq!(BigInt::<5>::MIN.as_significant_slice(), [9; 5]);
+        assert_eq!(BigInt::<1>::from_i128(-3).as_significant_slice(), [3]);
+        assert_eq!(BigInt::<1>::from_i128(0).as_significant_slice(), [0]);
+        assert_eq!(BigIntTest::pow10(99).as_significant_slice().len(), 100);
 
-            const EXPECTED: BigIntTest = BigInt::from_str(RESULT);
-            assert_eq!(Z, EXPECTED);
+        for x in random_values(67, 100) {
+            let expected: Vec<u8> = x.abs().to_string().bytes().map(|c| c - b'0').collect();
+            assert_eq!(x.as_significant_slice(), expected);
         }
+    }
+
+    #[test]
+    fn increment_decrement() {
+        const X: BigIntTest = BigInt::DEFAULT.decrement();
+        assert_eq!(X, BigInt::from_i128(-1));
+        assert_eq!(X.increment().to_string(), "0");
+        assert_eq!(X.increment().increment(), BigInt::from_i128(1));
 
+        let mut up = BigIntTest::from_i128(-1205);
+        let mut down = BigIntTest::from_i128(1205);
+        for x in -1205..=1205i128 {
+            assert_eq!(up, BigInt::from_i128(x));
+            assert_eq!(down, BigInt::from_i128(-x));
+            assert_eq!(up.size(), BigIntTest::from_i128(x).size());
+            up = up.increment();
+            down = down.decrement();
+        }
 
+        assert_eq!(BigIntTest::from_i128(999999).increment(), BigInt::from_i128(1000000));
+        assert_eq!(BigIntTest::from_i128(-1000000).increment(), BigInt::from_i128(-999999));
+        assert_eq!(BigIntTest::from_i128(-999999).decrement(), BigInt::from_i128(-1000000));
+        assert_eq!(BigIntTest::from_i128(1000000).decrement(), BigInt::from_i128(999999));
+        assert_eq!(BigInt::<3>::from_i128(998).increment(), BigInt::<3>::MAX);
     }
 
     #[test]
-    fn sub_2() {
-        for x in -1000..=1000i32 {
-            for y in -1000..=1000i32 {
-                let x_str = x.to_string();
-                let y_str = y.to_string();
-                let result = (x - y).to_string();
-                let x1: BigIntTest = BigInt::from_str(&x_str);
-                let y1: BigIntTest = BigInt::from_str(&y_str);
-                let result1: BigIntTest = x1 - y1;
-                let expected: BigIntTest = BigInt::from_str(&result);
-                assert_eq!(result1, expected, "{}", format!("{} - {} = {}", x, y, result));
-            }
+    fn shift_operators() {
+        let hundred: BigIntTest = BigInt::from_i128(100);
+        let ten: BigIntTest = BigInt::from_i128(10);
+        for x in [-987654321i128, -15, -1, 0, 1, 9, 10, 12345] {
+            let x1: BigIntTest = BigInt::from_i128(x);
+            assert_eq!(x1 << 2, x1 * hundred, "{} << 2", x);
+            assert_eq!(x1 >> 1, x1 / ten, "{} >> 1", x);
+            assert_eq!(x1 >> 1, BigInt::from_i128(x / 10), "{} >> 1", x);
+
+            let mut y = x1;
+            y <<= 3;
+            assert_eq!(y, BigInt::from_i128(x * 1000));
+            y >>= 4;
+            assert_eq!(y, BigInt::from_i128(x * 1000 / 10000));
         }
     }
 
     #[test]
-    fn mul(){
-        for x in -1000..=1000i32 {
-            for y in -1000..=1000i32 {
-                let x_str = x.to_string();
-                let y_str = y.to_string();
-                let result = (x * y).to_string();
-                let x1: BigIntTest = BigInt::from_str(&x_str);
-                let y1: BigIntTest = BigInt::from_str(&y_str);
-                let result1: BigIntTest = x1 * y1;
-                let expected: BigIntTest = BigInt::from_str(&result);
-                assert_eq!(result1, expected, "{}", format!("{} * {} = {}", x, y, result));
+    fn saturating_sub_to_zero() {
+        let (three, five): (BigIntTest, BigIntTest) = (BigInt::from_i128(3), BigInt::from_i128(5));
+        assert_eq!(three.saturating_sub_to_zero(five), BigInt::from_i128(0));
+        assert_eq!(five.saturating_sub_to_zero(three), BigInt::from_i128(2));
+        for x in -20..=20i128 {
+            for y in -20..=20i128 {
+                let result = BigIntTest::from_i128(x).saturating_sub_to_zero(BigInt::from_i128(y));
+                assert_eq!(result, BigInt::from_i128((x - y).max(0)), "{} - {}", x, y);
             }
         }
     }
 
     #[test]
-    fn div(){
-        type I3 = BigInt<3>;
-        for x in -999..=999i32 {
-            for y in -999..=999i32 {
-                if y == 0 {
-                    continue;
-                }
-                let x_str = x.to_string();
-                let y_str = y.to_string();
-                let result = (x / y).to_string();
-                let result_rem = (x % y).to_string();
-                let x1: I3 = BigInt::from_str(&x_str);
-                let y1: I3 = BigInt::from_str(&y_str);
-                let (result1, result2) = x1.div(y1);
-                let expected: I3 = BigInt::from_str(&result);
-                let expected_rem: I3 = BigInt::from_str(&result_rem);
-                assert_eq!(result1, expected, "{}", format!("{} / {} = {}", x, y, result));
-                assert_eq!(result2, expected_rem, "{}", format!("{} % {} = {}", x, y, result_rem));
+    fn split_at() {
+        const PARTS: (BigIntTest, BigIntTest) = BigInt::from_i128(-1234567).split_at(3);
+        assert_eq!(PARTS, (BigInt::from_i128(1234), BigInt::from_i128(567)));
+
+        let x = BigIntTest::from_i128(5000012);
+        assert_eq!(x.split_at(2), (BigInt::from_i128(50000), BigInt::from_i128(12)));
+        assert_eq!(x.split_at(5).1.size(), 2);
+        assert_eq!(x.split_at(0), (x, BigInt::from_i128(0)));
+        assert_eq!(x.split_at(0).1.size(), 1);
+        assert_eq!(x.split_at(7), (BigInt::from_i128(0), x));
+        assert_eq!(x.split_at(100), (BigInt::from_i128(0), x));
+
+        // zero, a full buffer and a single digit
+        for place in 0..3 {
+            let (high, low) = BigIntTest::from_i128(0).split_at(place);
+            assert_eq!((high.size(), low.size()), (1, 1));
+        }
+        assert_eq!(BigInt::<5>::MIN.split_at(4), (BigInt::from_i128(9), BigInt::from_i128(9999)));
+        assert_eq!(BigInt::<1>::from_i128(-7).split_at(0), (BigInt::from_i128(7), BigInt::from_i128(0)));
+        assert_eq!(BigInt::<1>::from_i128(-7).split_at(1), (BigInt::from_i128(0), BigInt::from_i128(7)));
+
+        for x in random_values(71, 90) {
+            for place in 0..=x.size() + 1 {
+                let (high, low) = x.split_at(place);
+                assert!(high >= 0 && low >= 0);
+                assert_eq!(high.shl_pow10(place) + low, x.abs(), "{} at {}", x, place);
             }
         }
     }
 
     #[test]
-    fn div_2() {
-        const A: BigIntTest = BigInt::from_str("33322211112345678987654321");
-        const B: BigIntTest = BigInt::from_str("15485863");
-        const DIV: BigIntTest = BigInt::from_str("2151782636353277759");
-        const REM: BigIntTest = BigInt::from_str("10833304");
+    fn count_digit() {
+        let x = BigIntTest::from_i128(-112233);
+        for (d, expected) in [(0, 0), (1, 2), (2, 2), (3, 2), (4, 0), (9, 0)] {
+            assert_eq!(x.count_digit(d), expected);
+        }
+        assert_eq!(BigIntTest::from_i128(0).count_digit(0), 1);
+        assert_eq!(BigIntTest::from_i128(1000).count_digit(0), 3);
 
-        const RES: (BigIntTest, BigIntTest) = A.div(B);
-        assert_eq!(RES.0, DIV);
-        assert_eq!(RES.1, REM);
+        // only significant digits count, however large the buffer
+        assert_eq!(BigInt::<5>::MIN.count_digit(9), 5);
+        assert_eq!(BigInt::<5>::MIN.count_digit(0), 0);
+        assert_eq!(BigInt::<1>::from_i128(0).count_digit(0), 1);
+        assert_eq!(BigInt::<1>::from_i128(-5).count_digit(5), 1);
+
+        for x in random_values(73, 100) {
+            let s = x.abs().to_string();
+            let total: usize = (0..=9).map(|d| x.count_digit(d)).sum();
+            assert_eq!(total, s.len());
+            assert_eq!(x.count_digit(7), s.matches('7').count());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn count_digit_invalid() {
+        BigIntTest::from_i128(0).count_digit(10);
+    }
+
+    #[test]
+    fn rotate() {
+        const X: BigIntTest = BigInt::from_i128(123456).rotate_left(2);
+        assert_eq!(X, BigInt::from_i128(345612));
+
+        let x = BigIntTest::from_i128(123456);
+        let left = [123456, 234561, 345612, 456123, 561234, 612345];
+        for n in 0..20 {
+            assert_eq!(x.rotate_left(n), BigInt::from_i128(left[n % 6]), "{}", n);
+            assert_eq!(x.rotate_right(n), BigInt::from_i128(left[(6 - n % 6) % 6]), "{}", n);
+            assert_eq!(x.rotate_left(n).rotate_right(n), x);
+            assert_eq!((-x).rotate_right(n), -x.rotate_right(n));
+        }
+
+        assert_eq!(BigIntTest::from_i128(102).rotate_left(1), BigInt::from_i128(21));
+        assert_eq!(BigIntTest::from_i128(102).rotate_left(1).size(), 2);
+        assert_eq!(BigIntTest::from_i128(-100).rotate_right(2), BigInt::from_i128(-1));
+        assert_eq!(BigIntTest::from_i128(0).rotate_left(3), BigInt::from_i128(0));
+        assert_eq!(BigIntTest::from_i128(7).rotate_right(5), BigInt::from_i128(7));
+    }
+
+    #[test]
+    fn from_str_or() {
+        const FALLBACK: BigIntTest = BigInt::from_i128(-1);
+        const X: BigIntTest = BigInt::from_str_or("-12345678901234567890", FALLBACK);
+        const BAD: BigIntTest = BigInt::from_str_or("12x45", FALLBACK);
+        assert_eq!(X, BigInt::from_str("-12345678901234567890"));
+        assert_eq!(BAD, FALLBACK);
+
+        for s in ["", "-", "+", "--1", "1-", "1 2", "abc", "1é"] {
+            assert_eq!(BigIntTest::from_str_or(s, FALLBACK), FALLBACK, "{:?}", s);
+        }
+        for s in ["0", "-0", "+0", "000", "+42", "-0042", "99999"] {
+            assert_eq!(BigInt::<5>::from_str_or(s, BigInt::from_i128(-1)), BigInt::try_from_str(s).unwrap(), "{:?}", s);
+        }
+        assert_eq!(BigInt::<5>::from_str_or("000099999", BigInt::from_i128(-1)), BigInt::from_i128(99999));
+        assert_eq!(BigInt::<5>::from_str_or("100000", BigInt::from_i128(-1)), BigInt::from_i128(-1));
+        assert_eq!(BigInt::<5>::from_str_or("-0", BigInt::from_i128(-1)).to_string(), "0");
+    }
+}
+
+// property tests against num-bigint as a reference implementation
+#[cfg(all(test, feature = "reference-tests"))]
+mod reference_tests {
+    use crate::bigint::BigInt;
+    use num_bigint::BigInt as Reference;
+    use proptest::prelude::*;
+
+    type I100 = BigInt<100>;
+    type I20 = BigInt<20>;
+    type I400 = BigInt<400>;
+
+    fn parse(s: &str) -> (I100, Reference) {
+        (I100::try_from_str(s).unwrap(), s.parse().unwrap())
+    }
+
+    /// Checks every operation on a pair against the reference
+    fn check_pair(a: &str, b: &str) {
+        let (x, rx) = parse(a);
+        let (y, ry) = parse(b);
+        assert_eq!(x.to_string(), rx.to_string());
+        assert_eq!((x + y).to_string(), (&rx + &ry).to_string(), "{} + {}", a, b);
+        assert_eq!((x - y).to_string(), (&rx - &ry).to_string(), "{} - {}", a, b);
+        assert_eq!((x * y).to_string(), (&rx * &ry).to_string(), "{} * {}", a, b);
+        assert_eq!(x.cmp(&y), rx.cmp(&ry), "{} cmp {}", a, b);
+        assert_eq!(x == y, rx == ry, "{} == {}", a, b);
+        if !y.is_zero() {
+            // num-bigint also truncates, with the remainder taking the sign of the dividend
+            assert_eq!((x / y).to_string(), (&rx / &ry).to_string(), "{} / {}", a, b);
+            assert_eq!((x % y).to_string(), (&rx % &ry).to_string(), "{} % {}", a, b);
+        }
+    }
+
+    /// Checks that the checked operations fail exactly when the result has more than 20 digits
+    fn check_overflow(a: &str, b: &str) {
+        let x = I20::try_from_str(a).unwrap();
+        let y = I20::try_from_str(b).unwrap();
+        let (rx, ry): (Reference, Reference) = (a.parse().unwrap(), b.parse().unwrap());
+        let fits = |r: &Reference| r.magnitude().to_string().len() <= 20;
+
+        let sum = &rx + &ry;
+        assert_eq!(x.checked_add(y).map(|r| r.to_string()), fits(&sum).then(|| sum.to_string()), "{} + {}", a, b);
+        let difference = &rx - &ry;
+        assert_eq!(x.checked_sub(y).map(|r| r.to_string()), fits(&difference).then(|| difference.to_string()), "{} - {}", a, b);
+        let product = &rx * &ry;
+        assert_eq!(x.checked_mul(y).map(|r| r.to_string()), fits(&product).then(|| product.to_string()), "{} * {}", a, b);
+    }
+
+    /// Checks products of operands long enough for Karatsuba, and dividing them
+    /// plus an offset by each operand, against the reference
+    fn check_large(a: &str, b: &str, offset: &str) {
+        let (x, rx) = (I400::try_from_str(a).unwrap(), a.parse::<Reference>().unwrap());
+        let (y, ry) = (I400::try_from_str(b).unwrap(), b.parse::<Reference>().unwrap());
+        let (z, rz) = (I400::try_from_str(offset).unwrap(), offset.parse::<Reference>().unwrap());
+
+        let product = x * y;
+        let reference = &rx * &ry;
+        assert_eq!(product.to_string(), reference.to_string(), "{} * {}", a, b);
+
+        let dividend = product + z;
+        let reference = reference + rz;
+        for (divisor, reference_divisor) in [(x, &rx), (y, &ry)] {
+            assert_eq!((dividend / divisor).to_string(), (&reference / reference_divisor).to_string(), "{} / {}", dividend, divisor);
+            assert_eq!((dividend % divisor).to_string(), (&reference % reference_divisor).to_string(), "{} % {}", dividend, divisor);
+        }
+    }
+
+    #[test]
+    fn negative_dividends() {
+        let cases = [
+            ("-7", "2"), ("-7", "-2"), ("7", "-2"), ("-6", "3"), ("-1", "5"), ("-5", "5"),
+            ("-100", "7"), ("-999999999999", "1000"), ("-1000000000000", "-999999"),
+            ("-123456789012345678901234567890", "987654321"),
+            ("-99999999999999999999", "-99999999999999999999"), ("-0", "3"), ("0", "-3"),
+        ];
+        for (a, b) in cases {
+            check_pair(a, b);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn matches_reference(a in "-?[0-9]{1,45}", b in "-?[0-9]{1,45}") {
+            check_pair(&a, &b);
+        }
+
+        #[test]
+        fn matches_reference_small_divisor(a in "-?[0-9]{1,45}", b in "-?[0-9]{1,3}") {
+            check_pair(&a, &b);
+        }
+
+        // Karatsuba only kicks in above 64 digits
+        #[test]
+        fn large_matches_reference(a in "-?[1-9][0-9]{64,199}", b in "-?[1-9][0-9]{64,199}", offset in "-?[0-9]{1,150}") {
+            check_large(&a, &b, &offset);
+        }
+
+        #[test]
+        fn overflow_matches_reference(a in "-?[0-9]{1,20}", b in "-?[0-9]{1,20}") {
+            check_overflow(&a, &b);
+        }
     }
 }
@@ -1,3 +1,5 @@
+// not every method of the copy-paste module is used by this demo
+#[allow(dead_code)]
 mod bigint;
 
 fn main() {